@@ -0,0 +1,233 @@
+//! Derive macros for `hpc-core`.
+//!
+//! `#[derive(KernelArgs)]` binds the fields of a struct to a kernel in
+//! declaration order. Every field must implement `hpc_core::KernelArg`
+//! (a `DeviceBuffer<'brand, T, Written>`, a `KernelScalar` or a
+//! `LocalMem<T>`); buffer fields move to `InFlight` at launch. `KernelArg`
+//! is sealed, so these are the only field types; the generated code goes
+//! through the hidden `hpc_core::__private` module.
+//!
+//! ```ignore
+//! #[derive(KernelArgs)]
+//! struct VecAdd<'brand> {
+//!     a: DeviceBuffer<'brand, f32, Written>,
+//!     b: DeviceBuffer<'brand, f32, Written>,
+//!     out: DeviceBuffer<'brand, f32, Written>,
+//!     n: u32,
+//! }
+//!
+//! let ((a, b, out, ()), evt) = kernel.launch(&queue, VecAdd { a, b, out, n }, n as usize)?;
+//! ```
+//!
+//! `#[derive(KernelScalar)]` makes a `#[repr(C)]` struct usable as a
+//! by-value kernel argument and as buffer element. The struct must also
+//! derive `bytemuck::Pod` (which rejects padding), every field must be a
+//! `KernelScalar`, and every field must be aligned on the host as in
+//! OpenCL C (`KernelScalar::DEVICE_ALIGN`). `repr(packed)` is rejected.
+//!
+//! ```ignore
+//! #[repr(C)]
+//! #[derive(Clone, Copy, Pod, Zeroable, KernelScalar)]
+//! struct Params {
+//!     origin: Float4,
+//!     scale: f32,
+//!     steps: u32,
+//!     _pad: UInt2,
+//! }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields, Index, Lifetime, LifetimeParam, Type, parse_macro_input};
+
+#[proc_macro_derive(KernelArgs)]
+pub fn derive_kernel_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(KernelScalar)]
+pub fn derive_kernel_scalar(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_scalar(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "KernelArgs can only be derived for structs",
+            ));
+        }
+    };
+
+    // Field accessors in declaration order (named or tuple struct)
+    let (members, types): (Vec<TokenStream2>, Vec<&syn::Type>) = match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|f| {
+                let ident = f.ident.as_ref().expect("named field");
+                (quote!(#ident), &f.ty)
+            })
+            .unzip(),
+        Fields::Unnamed(unnamed) => unnamed
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let index = Index::from(i);
+                (quote!(#index), &f.ty)
+            })
+            .unzip(),
+        Fields::Unit => (Vec::new(), Vec::new()),
+    };
+
+    if members.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "KernelArgs needs at least one field",
+        ));
+    }
+    if members.len() > 16 {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "KernelArgs supports at most 16 fields",
+        ));
+    }
+
+    // The first lifetime parameter is the brand; structs with only scalar
+    // fields have none and get a fresh one.
+    let mut impl_generics_src = input.generics.clone();
+    let brand = match input.generics.lifetimes().next() {
+        Some(param) => param.lifetime.clone(),
+        None => {
+            let brand = Lifetime::new("'__brand", Span::call_site());
+            impl_generics_src
+                .params
+                .insert(0, LifetimeParam::new(brand.clone()).into());
+            brand
+        }
+    };
+    let (impl_generics, _, _) = impl_generics_src.split_for_impl();
+    let (sealed_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Spans on the field types, so a field that is no `KernelArg` is reported there
+    let inflight = types
+        .iter()
+        .map(|ty| quote_spanned!(ty.span()=> <#ty as ::hpc_core::KernelArg<#brand>>::InFlight));
+    let binds = members.iter().zip(&types).map(|(member, ty)| {
+        quote_spanned!(ty.span()=> <#ty as ::hpc_core::KernelArg<#brand>>::bind(self.#member, binder)?)
+    });
+
+    Ok(quote! {
+        impl #sealed_generics ::hpc_core::__private::ArgsSealed for #name #ty_generics #where_clause {}
+
+        impl #impl_generics ::hpc_core::KernelArgs<#brand> for #name #ty_generics #where_clause {
+            type InFlight = (#(#inflight,)*);
+
+            fn bind(
+                self,
+                binder: &mut ::hpc_core::__private::ArgBinder<'_, #brand>,
+            ) -> ::hpc_core::Result<Self::InFlight> {
+                Ok((#(#binds,)*))
+            }
+        }
+    })
+}
+
+//#####
+// KERNEL SCALAR
+//#####
+
+fn expand_scalar(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "KernelScalar can only be derived for structs",
+            ));
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "KernelScalar cannot be derived for generic structs",
+        ));
+    }
+    if fields.is_empty() {
+        return Err(syn::Error::new_spanned(
+            name,
+            "KernelScalar needs at least one field",
+        ));
+    }
+    check_repr(&input)?;
+
+    // Pro Feld: KernelScalar + gleiche Ausrichtung wie in OpenCL C
+    let checks = fields.iter().enumerate().map(|(i, f)| {
+        let ty: &Type = &f.ty;
+        let field = f
+            .ident
+            .as_ref()
+            .map_or_else(|| i.to_string(), ToString::to_string);
+        let msg = format!(
+            "field `{field}` of `{name}` is aligned differently on the host than in OpenCL C"
+        );
+        quote_spanned! {ty.span()=>
+            ::core::assert!(
+                ::core::mem::align_of::<#ty>() == <#ty as ::hpc_core::KernelScalar>::DEVICE_ALIGN,
+                #msg
+            );
+        }
+    });
+
+    Ok(quote! {
+        impl ::hpc_core::KernelScalar for #name {}
+
+        const _: () = {
+            #(#checks)*
+        };
+    })
+}
+
+/// Requires `#[repr(C)]` and rejects `packed`.
+fn check_repr(input: &DeriveInput) -> syn::Result<()> {
+    let mut has_c = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                has_c = true;
+            } else if meta.path.is_ident("packed") {
+                return Err(meta.error(
+                    "KernelScalar structs cannot be packed, OpenCL C aligns struct fields",
+                ));
+            } else if meta.input.peek(syn::token::Paren) {
+                // align(N) ist erlaubt
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        })?;
+    }
+    if !has_c {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "KernelScalar requires #[repr(C)] so the field layout matches OpenCL C",
+        ));
+    }
+    Ok(())
+}
@@ -252,19 +252,17 @@ fn api_full_bench(c: &mut Criterion) {
                     .write_block(&queue, black_box(&result))
                     .unwrap();
 
-                // 2. Kernel-Argumente setzen
-                kernel.set_arg_buffer(0, &buffer_a).unwrap();
-                kernel.set_arg_buffer(1, &buffer_b).unwrap();
-                kernel.set_arg_buffer(2, &buffer_result).unwrap();
-                kernel.set_arg_scalar(3, &(nbytes as u32)).unwrap();
-
-                // 3. Kernel ausführen
-                let (inflight_buffer, event) = buffer_result
-                    .enqueue_kernel(&queue, &kernel, nbytes)
+                // 2. Kernel ausführen (bindet alle Argumente, Buffer → InFlight)
+                let (inflight, event) = kernel
+                    .launch(
+                        &queue,
+                        (buffer_a, buffer_b, buffer_result, nbytes as u32),
+                        nbytes,
+                    )
                     .unwrap();
 
-                // 4. Warten und Ergebnis zurücklesen
-                let result_buffer = event.wait(inflight_buffer);
+                // 3. Warten und Ergebnis zurücklesen
+                let (_a, _b, result_buffer, ()) = event.wait(inflight).unwrap();
                 result_buffer
                     .read_blocking(&queue, black_box(&mut result))
                     .unwrap();
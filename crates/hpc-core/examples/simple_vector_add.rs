@@ -41,9 +41,7 @@ fn main() -> Result<()> {
         .create_empty_buffer::<u32>(size)?
         .write_block(&queue, &b)?; // Empty → Written
 
-    let buffer_result = ctx
-        .create_empty_buffer::<u32>(size)?
-        .write_block(&queue, &result)?; // Empty → Written (mit Nullen)
+    let buffer_result = ctx.create_empty_buffer::<u32>(size)?.fill(&queue, 0)?; // Empty → Written (mit Nullen, ohne Host-Transfer)
 
     println!("Buffers created and initialized");
 
@@ -51,43 +49,36 @@ fn main() -> Result<()> {
     let kernel = Kernel::from_source(&ctx, kernel_source, "vector_add")?;
     println!("Kernel compiled");
 
-    // 6. Kernel-Argumente setzen
-    kernel.set_arg_buffer(0, &buffer_a)?; // Input A
-    kernel.set_arg_buffer(1, &buffer_b)?; // Input B  
-    kernel.set_arg_buffer(2, &buffer_result)?; // Output
-    kernel.set_arg_scalar(3, &(size as u32))?; // Size parameter
-
-    println!("Kernel arguments set");
-
-    // 7. Kernel ausführen
-    let (inflight_buffer, event) = buffer_result.enqueue_kernel(&queue, &kernel, size)?;
+    // 6. Kernel ausführen: alle Argumente in Reihenfolge, Buffer → InFlight
+    let (inflight, event) = kernel.launch(
+        &queue,
+        (buffer_a, buffer_b, buffer_result, size as u32),
+        size,
+    )?;
     println!("Kernel started");
 
-    // 8. Warten bis Kernel fertig ist und zurück zu Written
-    let result_buffer = event.wait(inflight_buffer); // InFlight → Written (kein ?)
+    // 7. Warten bis Kernel fertig ist und zurück zu Written
+    let (_a, _b, result_buffer, ()) = event.wait(inflight)?; // InFlight → Written
     println!("Kernel completed");
 
-    // 9. Ergebnis zurück lesen
+    // 8. Ergebnis zurück lesen
     result_buffer.read_blocking(&queue, &mut result)?;
     println!("Results read back");
 
-    // 10. Erste 100 Ergebnisse anzeigen
+    // 9. Erste 100 Ergebnisse anzeigen
     println!("\nFirst 100 results of vector addition:");
     for i in 0..100.min(size) {
         println!("{:3}: {:3} + {:3} = {:3}", i, a[i], b[i], result[i]);
     }
 
-    // 11. Vollständige Verifikation (sollte überall 2 sein)
+    // 10. Vollständige Verifikation (sollte überall 2 sein)
     let mut errors = 0;
-    for i in 0..size {
+    for (i, &value) in result.iter().enumerate() {
         let expected = 2u32; // 1 + 1 = 2
-        if result[i] != expected {
+        if value != expected {
             if errors < 5 {
                 // Only show first 5 errors
-                println!(
-                    "Error at index {}: expected {}, got {}",
-                    i, expected, result[i]
-                );
+                println!("Error at index {}: expected {}, got {}", i, expected, value);
             }
             errors += 1;
         }
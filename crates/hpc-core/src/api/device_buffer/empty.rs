@@ -6,13 +6,13 @@ use crate::api::util::MapToken;
 use crate::buffer::state::{Empty, Mapped, Written};
 use crate::error::{Error, Result};
 
-use crate::api::Queue;
+use crate::api::{ProfilingMode, Queue};
 
 impl<'brand, T> DeviceBuffer<'brand, T, Empty> {
-    pub fn map_for_write_block(
+    pub fn map_for_write_block<'q, P: ProfilingMode>(
         self,
-        queue: &'brand Queue<'brand>, // <-- 'brand explizit hinzufügen
-    ) -> Result<(DeviceBuffer<'brand, T, Mapped>, MapToken<'brand>)>
+        queue: &'q Queue<'brand, P>,
+    ) -> Result<(DeviceBuffer<'brand, T, Mapped>, MapToken<'q>)>
     where
         T: bytemuck::Pod,
     {
@@ -31,9 +31,9 @@ impl<'brand, T> DeviceBuffer<'brand, T, Empty> {
         Ok((DeviceBuffer::from_inner(inner_mapped, self.len), map_token))
     }
 
-    pub fn write_block(
+    pub fn write_block<P: ProfilingMode>(
         self,
-        queue: &Queue<'brand>,
+        queue: &Queue<'brand, P>,
         data: &[T],
     ) -> Result<DeviceBuffer<'brand, T, Written>>
     where
@@ -0,0 +1,46 @@
+//! Device-side initialization with a repeated value (`clEnqueueFillBuffer`).
+//!
+//! Available in `Empty` and `Written`: no host allocation and no transfer,
+//! which matters for large scratch buffers.
+//!
+//! ```ignore
+//! let out = ctx.create_empty_buffer::<f32>(n)?.fill(&queue, 0.0)?; // Empty → Written
+//! ```
+
+use super::DeviceBuffer;
+use crate::EventToken;
+use crate::api::{ProfilingMode, Queue};
+use crate::buffer::state::{InFlight, Overwritable, Written};
+use crate::error::Result;
+
+impl<'brand, T: bytemuck::Pod, S: Overwritable> DeviceBuffer<'brand, T, S> {
+    /// Set every element to `value` and wait for completion.
+    ///
+    /// `size_of::<T>()` must be a power of two up to 128 bytes.
+    pub fn fill<P: ProfilingMode>(
+        self,
+        queue: &Queue<'brand, P>,
+        value: T,
+    ) -> Result<DeviceBuffer<'brand, T, Written>> {
+        let (buf, evt) = self.fill_non_blocking(queue, value)?;
+        evt.wait(buf)
+    }
+
+    /// Like `fill`, but returns immediately; the buffer is `InFlight` until
+    /// the token is waited on.
+    #[must_use = "GPU fill started: you must wait for completion"]
+    pub fn fill_non_blocking<P: ProfilingMode>(
+        self,
+        queue: &Queue<'brand, P>,
+        value: T,
+    ) -> Result<(DeviceBuffer<'brand, T, InFlight>, EventToken<'brand, P>)> {
+        let (inner_inflight, evt) = self
+            .inner
+            .enqueue_fill(queue.raw(), bytemuck::bytes_of(&value))?;
+
+        Ok((
+            DeviceBuffer::from_inner(inner_inflight, self.len),
+            EventToken::from_event(evt),
+        ))
+    }
+}
@@ -1,7 +1,86 @@
 // src/api/inflight.rs
 
+//! Sets of in-flight buffers.
+//!
+//! A kernel launch moves every buffer argument to `InFlight` at once and
+//! hands them back as one value (a buffer, a tuple or `()`). `EventToken::wait`
+//! turns the whole set back into `Written`.
+
+use super::DeviceBuffer;
+use crate::buffer::state::{InFlight, Written};
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+
+    /// Only the crate can create this, so only the crate can call
+    /// `InFlightBuffers::complete`.
+    pub struct Token(pub(crate) ());
+}
+
 //#####
 // INFLIGHT STATE IMPLEMENTATIONS
 //#####
 
-//No implementation needed. InFlight doesn't provide any functions
+/// One or more buffers of brand `'brand` in state `InFlight`.
+///
+/// Implemented for `DeviceBuffer<'brand, T, InFlight>`, `()` (scalar-only
+/// arguments) and tuples of these. Sealed.
+pub trait InFlightBuffers<'brand>: sealed::Sealed {
+    /// The same set in state `Written`.
+    type Written;
+
+    #[doc(hidden)]
+    fn complete(self, _: sealed::Token) -> Self::Written;
+}
+
+impl<T> sealed::Sealed for DeviceBuffer<'_, T, InFlight> {}
+
+impl<'brand, T> InFlightBuffers<'brand> for DeviceBuffer<'brand, T, InFlight> {
+    type Written = DeviceBuffer<'brand, T, Written>;
+
+    fn complete(self, _: sealed::Token) -> Self::Written {
+        let inner = unsafe { self.inner.assume_state::<Written>() };
+        DeviceBuffer::from_inner(inner, self.len)
+    }
+}
+
+impl sealed::Sealed for () {}
+
+impl InFlightBuffers<'_> for () {
+    type Written = ();
+
+    fn complete(self, _: sealed::Token) {}
+}
+
+macro_rules! impl_inflight_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: sealed::Sealed),+> sealed::Sealed for ($($name,)+) {}
+
+        impl<'brand, $($name: InFlightBuffers<'brand>),+> InFlightBuffers<'brand> for ($($name,)+) {
+            type Written = ($($name::Written,)+);
+
+            #[allow(non_snake_case)]
+            fn complete(self, _: sealed::Token) -> Self::Written {
+                let ($($name,)+) = self;
+                ($($name.complete(sealed::Token(())),)+)
+            }
+        }
+    };
+}
+
+impl_inflight_tuple!(A);
+impl_inflight_tuple!(A, B);
+impl_inflight_tuple!(A, B, C);
+impl_inflight_tuple!(A, B, C, D);
+impl_inflight_tuple!(A, B, C, D, E);
+impl_inflight_tuple!(A, B, C, D, E, F);
+impl_inflight_tuple!(A, B, C, D, E, F, G);
+impl_inflight_tuple!(A, B, C, D, E, F, G, H);
+impl_inflight_tuple!(A, B, C, D, E, F, G, H, I);
+impl_inflight_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_inflight_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_inflight_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_inflight_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_inflight_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_inflight_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_inflight_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q);
@@ -13,7 +13,7 @@ use crate::error::{Error, Result};
 
 impl<'brand, T> DeviceBuffer<'brand, T, Mapped> {
     /// Write data directly to the mapped memory (blocking)
-    pub fn write_blocking(&mut self, data: &[T], token: &mut MapToken<'brand>) -> Result<()>
+    pub fn write_blocking(&mut self, data: &[T], token: &mut MapToken<'_>) -> Result<()>
     where
         T: bytemuck::Pod + Copy,
     {
@@ -40,7 +40,7 @@ impl<'brand, T> DeviceBuffer<'brand, T, Mapped> {
     }
 
     /// Read data from the mapped memory (blocking)
-    pub fn read_blocking(&self, output: &mut [T], token: &MapToken<'brand>) -> Result<()>
+    pub fn read_blocking(&self, output: &mut [T], token: &MapToken<'_>) -> Result<()>
     where
         T: bytemuck::Pod + Copy,
     {
@@ -1,14 +1,20 @@
 // src/api/device_buffer/mod.rs
 
 use crate::buffer::{GpuBuffer, state::State};
+use crate::error::{Error, Result};
 use std::marker::PhantomData;
+use std::ops::Range;
 
 // State-specific implementations
 mod empty;
+mod fill;
 mod inflight;
 mod mapped;
 mod written;
 
+pub use inflight::InFlightBuffers;
+pub(crate) use inflight::sealed::Token as CompleteToken;
+
 //=============================================================================
 // STRUCT DEFINITION
 //=============================================================================
@@ -34,4 +40,98 @@ impl<'ctx, T, S: State> DeviceBuffer<'ctx, T, S> {
             _type: PhantomData,
         }
     }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Check an element `range` against the buffer and the host slice it is
+/// transferred from/to. Reversed and empty ranges are `Error::OutOfBounds`.
+pub(crate) fn check_host_range(
+    range: &Range<usize>,
+    host_len: usize,
+    buffer_len: usize,
+) -> Result<()> {
+    let Some(len) = range.end.checked_sub(range.start) else {
+        return Err(Error::OutOfBounds {
+            offset: range.start,
+            len: 0,
+            buffer_len,
+        });
+    };
+    if len != host_len {
+        return Err(Error::BufferSizeMismatch {
+            expected: len,
+            actual: host_len,
+        });
+    }
+    check_range(range.start, len, buffer_len)
+}
+
+/// Check that `len` elements at `offset` lie within a buffer of `buffer_len`.
+///
+/// Empty ranges are rejected too: OpenCL fails transfers and copies of 0
+/// bytes with `CL_INVALID_VALUE`, after the buffers were already moved.
+pub(crate) fn check_range(offset: usize, len: usize, buffer_len: usize) -> Result<()> {
+    match offset.checked_add(len) {
+        Some(end) if len > 0 && end <= buffer_len => Ok(()),
+        _ => Err(Error::OutOfBounds {
+            offset,
+            len,
+            buffer_len,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn out_of_bounds(r: Result<()>) -> bool {
+        matches!(r, Err(Error::OutOfBounds { .. }))
+    }
+
+    #[test]
+    fn check_range_bounds() {
+        assert!(check_range(0, 10, 10).is_ok());
+        assert!(check_range(6, 4, 10).is_ok());
+        assert!(out_of_bounds(check_range(7, 4, 10)));
+        assert!(out_of_bounds(check_range(10, 1, 10)));
+        assert!(out_of_bounds(check_range(3, 0, 10)));
+        assert!(out_of_bounds(check_range(10, 0, 10)));
+        assert!(out_of_bounds(check_range(usize::MAX, 1, 10)));
+        assert!(out_of_bounds(check_range(1, usize::MAX, usize::MAX)));
+    }
+
+    #[test]
+    fn check_host_range_bounds() {
+        assert!(check_host_range(&(2..10), 8, 10).is_ok());
+        assert!(out_of_bounds(check_host_range(&(3..11), 8, 10)));
+        let reversed = Range { start: 10, end: 5 };
+        assert!(out_of_bounds(check_host_range(&reversed, 0, 10)));
+        assert!(out_of_bounds(check_host_range(&(4..4), 0, 10)));
+        assert!(out_of_bounds(check_host_range(
+            &(usize::MAX..usize::MAX),
+            0,
+            10
+        )));
+        assert!(out_of_bounds(check_host_range(
+            &(usize::MAX - 1..usize::MAX),
+            1,
+            10
+        )));
+        assert!(matches!(
+            check_host_range(&(0..4), 3, 10),
+            Err(Error::BufferSizeMismatch {
+                expected: 4,
+                actual: 3
+            })
+        ));
+    }
 }
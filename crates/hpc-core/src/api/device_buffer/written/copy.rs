@@ -0,0 +1,93 @@
+//! Device-side copies for `DeviceBuffer<T, Written>` (`clEnqueueCopyBuffer`).
+//!
+//! Source and destination both move to `InFlight`; one `EventToken` covers
+//! the copy and gives both back as `Written`:
+//!
+//! ```ignore
+//! let ((src, dst), evt) = src.copy_to(&queue, dst)?;
+//! let (src, dst) = evt.wait((src, dst))?;
+//! ```
+//!
+//! Both buffers are taken by value, so they are always distinct and the
+//! overlapping copy OpenCL forbids cannot be expressed.
+
+use crate::EventToken;
+use crate::api::device_buffer::check_range;
+use crate::api::{DeviceBuffer, ProfilingMode, Queue};
+use crate::buffer::state::{InFlight, Overwritable, Written};
+use crate::error::{Error, Result};
+
+pub(super) type CopyResult<'brand, T, P> = Result<(
+    (
+        DeviceBuffer<'brand, T, InFlight>,
+        DeviceBuffer<'brand, T, InFlight>,
+    ),
+    EventToken<'brand, P>,
+)>;
+
+impl<'brand, T> DeviceBuffer<'brand, T, Written> {
+    /// Copy the whole buffer into `dst` (`Empty` or `Written`) of the same length.
+    #[must_use = "GPU copy started: you must wait for completion"]
+    pub fn copy_to<P: ProfilingMode, S: Overwritable>(
+        self,
+        queue: &Queue<'brand, P>,
+        dst: DeviceBuffer<'brand, T, S>,
+    ) -> CopyResult<'brand, T, P> {
+        if dst.len != self.len {
+            return Err(Error::BufferSizeMismatch {
+                expected: self.len,
+                actual: dst.len,
+            });
+        }
+        let len = self.len;
+        self.copy(queue, dst, 0, 0, len)
+    }
+
+    /// Copy `len` elements from `src_offset` into `dst` at `dst_offset`.
+    ///
+    /// `dst` must already be `Written`: a partial copy into an `Empty`
+    /// buffer would leave the rest of it uninitialised. `len == 0` fails
+    /// with `Error::OutOfBounds` before either buffer is moved.
+    #[must_use = "GPU copy started: you must wait for completion"]
+    pub fn copy_range_to<P: ProfilingMode>(
+        self,
+        queue: &Queue<'brand, P>,
+        src_offset: usize,
+        dst: DeviceBuffer<'brand, T, Written>,
+        dst_offset: usize,
+        len: usize,
+    ) -> CopyResult<'brand, T, P> {
+        // Kein Überlappungstest nötig: self und dst sind zwei verschiedene
+        // Werte, derselbe Puffer kann wegen Move/Aliasing nicht beide sein
+        check_range(src_offset, len, self.len)?;
+        check_range(dst_offset, len, dst.len)?;
+        self.copy(queue, dst, src_offset, dst_offset, len)
+    }
+
+    fn copy<P: ProfilingMode, S: Overwritable>(
+        self,
+        queue: &Queue<'brand, P>,
+        dst: DeviceBuffer<'brand, T, S>,
+        src_offset: usize,
+        dst_offset: usize,
+        len: usize,
+    ) -> CopyResult<'brand, T, P> {
+        let (src_len, dst_len) = (self.len, dst.len);
+        let elem = size_of::<T>();
+        let (src_inflight, dst_inflight, evt) = self.inner.enqueue_copy(
+            queue.raw(),
+            dst.inner,
+            src_offset * elem,
+            dst_offset * elem,
+            len * elem,
+        )?;
+
+        Ok((
+            (
+                DeviceBuffer::from_inner(src_inflight, src_len),
+                DeviceBuffer::from_inner(dst_inflight, dst_len),
+            ),
+            EventToken::from_event(evt),
+        ))
+    }
+}
@@ -4,16 +4,25 @@
 //! - Blocking read: copies data to host memory and keeps buffer in Written.
 //! - Non-blocking read: enqueues transfer, returns an EventToken to
 //!   synchronize completion.
+//! - `read_range*`: same for a range of elements, e.g. a window of a
+//!   large buffer.
 
+use std::ops::Range;
+
+use crate::api::device_buffer::check_host_range;
 use crate::api::util::{EventToken, ReadGuard};
-use crate::api::{DeviceBuffer, Queue};
+use crate::api::{DeviceBuffer, ProfilingMode, Queue};
 use crate::buffer::state::{InFlight, Written};
 use crate::error::{Error, Result};
 
 impl<'brand, T> DeviceBuffer<'brand, T, Written> {
     //############################READING FUNCTIONS
 
-    pub fn read_blocking(&self, queue: &Queue<'brand>, out: &mut [T]) -> Result<()>
+    pub fn read_blocking<P: ProfilingMode>(
+        &self,
+        queue: &Queue<'brand, P>,
+        out: &mut [T],
+    ) -> Result<()>
     where
         T: bytemuck::Pod,
     {
@@ -32,11 +41,14 @@ impl<'brand, T> DeviceBuffer<'brand, T, Written> {
         Ok(())
     }
 
-    pub fn read_non_blocking<'a>(
+    pub fn read_non_blocking<'a, P: ProfilingMode>(
         self,
-        queue: &Queue<'brand>,
+        queue: &Queue<'brand, P>,
         out: &'a mut [T],
-    ) -> Result<(DeviceBuffer<'brand, T, InFlight>, ReadGuard<'a, 'brand, T>)>
+    ) -> Result<(
+        DeviceBuffer<'brand, T, InFlight>,
+        ReadGuard<'a, 'brand, T, P>,
+    )>
     where
         T: bytemuck::Pod,
     {
@@ -60,4 +72,59 @@ impl<'brand, T> DeviceBuffer<'brand, T, Written> {
 
         Ok((DeviceBuffer::from_inner(inner_inflight, self.len), guard))
     }
+
+    /// Blocking read of the elements in `range` into `out` (`out.len() == range.len()`).
+    ///
+    /// Reversed, empty or out-of-buffer ranges fail with `Error::OutOfBounds`.
+    pub fn read_range<P: ProfilingMode>(
+        &self,
+        queue: &Queue<'brand, P>,
+        range: Range<usize>,
+        out: &mut [T],
+    ) -> Result<()>
+    where
+        T: bytemuck::Pod,
+    {
+        check_host_range(&range, out.len(), self.len)?;
+
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(out);
+        self.inner.enqueue_read_at(
+            queue.raw(),
+            range.start * size_of::<T>(),
+            bytes,
+            opencl3::types::CL_BLOCKING,
+        )?;
+
+        Ok(())
+    }
+
+    /// Non-blocking `read_range`; `out` stays borrowed by the guard.
+    pub fn read_range_non_blocking<'a, P: ProfilingMode>(
+        self,
+        queue: &Queue<'brand, P>,
+        range: Range<usize>,
+        out: &'a mut [T],
+    ) -> Result<(
+        DeviceBuffer<'brand, T, InFlight>,
+        ReadGuard<'a, 'brand, T, P>,
+    )>
+    where
+        T: bytemuck::Pod,
+    {
+        check_host_range(&range, out.len(), self.len)?;
+
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(out);
+        let evt = self.inner.enqueue_read_at(
+            queue.raw(),
+            range.start * size_of::<T>(),
+            bytes,
+            opencl3::types::CL_NON_BLOCKING,
+        )?;
+
+        let guard = ReadGuard::new(out, EventToken::from_event(evt));
+        Ok((
+            DeviceBuffer::from_inner(self.inner.into_inflight(), self.len),
+            guard,
+        ))
+    }
 }
@@ -3,13 +3,19 @@
 //! Provides blocking and non-blocking host write methods.
 //! - Blocking: copies data into the buffer and waits for completion.
 //! - Non-blocking: enqueues write and returns an `EventToken` for sync.
+//! - `write_range*`: update a range of elements only. Only on `Written`
+//!   buffers, a partial write would leave an `Empty` buffer half
+//!   uninitialised.
+
+use std::ops::Range;
 
 use crate::EventToken;
-use crate::api::util::MapToken;
-use crate::api::{DeviceBuffer, Queue};
+use crate::api::device_buffer::check_host_range;
+use crate::api::util::{MapToken, WriteGuard};
+use crate::api::{DeviceBuffer, ProfilingMode, Queue};
 use crate::buffer::state::{InFlight, Mapped, Written};
 use crate::error::{Error, Result};
-use opencl3::types::CL_BLOCKING;
+use opencl3::types::{CL_BLOCKING, CL_NON_BLOCKING};
 
 //#####
 // WRITE OPERATIONS
@@ -17,9 +23,9 @@ use opencl3::types::CL_BLOCKING;
 
 impl<'brand, T> DeviceBuffer<'brand, T, Written> {
     ///Performs blocking write for api DeviceBuffer
-    pub fn write_blocking(
+    pub fn write_blocking<P: ProfilingMode>(
         self,
-        queue: &Queue<'brand>,
+        queue: &Queue<'brand, P>,
         data: &[T],
     ) -> Result<DeviceBuffer<'brand, T, Written>>
     where
@@ -39,8 +45,11 @@ impl<'brand, T> DeviceBuffer<'brand, T, Written> {
     ///   overlapping asynchronous writes.
     ///
     /// Not intended for normal API usage; prefer the standard write methods
-
-    pub fn overwrite_blocking_for_bench(&mut self, queue: &Queue<'brand>, data: &[T]) -> Result<()>
+    pub fn overwrite_blocking_for_bench<P: ProfilingMode>(
+        &mut self,
+        queue: &Queue<'brand, P>,
+        data: &[T],
+    ) -> Result<()>
     where
         T: bytemuck::Pod,
     {
@@ -50,11 +59,11 @@ impl<'brand, T> DeviceBuffer<'brand, T, Written> {
     }
 
     /// like write. None blocke alternative.
-    pub fn write_non_block(
+    pub fn write_non_block<P: ProfilingMode>(
         self,
-        queue: &Queue<'brand>,
+        queue: &Queue<'brand, P>,
         data: &[T],
-    ) -> Result<(DeviceBuffer<'brand, T, InFlight>, EventToken<'brand>)>
+    ) -> Result<(DeviceBuffer<'brand, T, InFlight>, EventToken<'brand, P>)>
     where
         T: bytemuck::Pod,
     {
@@ -69,10 +78,10 @@ impl<'brand, T> DeviceBuffer<'brand, T, Written> {
     }
 
     ///we use this function for a mapped write. Only used in the Mapped state
-    pub fn map_for_write_block(
+    pub fn map_for_write_block<'q, P: ProfilingMode>(
         self,
-        queue: &'brand Queue<'brand>,
-    ) -> Result<(DeviceBuffer<'brand, T, Mapped>, MapToken<'brand>)>
+        queue: &'q Queue<'brand, P>,
+    ) -> Result<(DeviceBuffer<'brand, T, Mapped>, MapToken<'q>)>
     where
         T: bytemuck::Pod,
     {
@@ -89,4 +98,59 @@ impl<'brand, T> DeviceBuffer<'brand, T, Written> {
 
         Ok((DeviceBuffer::from_inner(inner_mapped, self.len), map_token))
     }
+
+    /// Blocking write of `data` to the elements in `range` (`data.len() == range.len()`).
+    ///
+    /// Reversed, empty or out-of-buffer ranges fail with `Error::OutOfBounds`.
+    pub fn write_range<P: ProfilingMode>(
+        mut self,
+        queue: &Queue<'brand, P>,
+        range: Range<usize>,
+        data: &[T],
+    ) -> Result<DeviceBuffer<'brand, T, Written>>
+    where
+        T: bytemuck::Pod,
+    {
+        check_host_range(&range, data.len(), self.len)?;
+
+        let bytes = bytemuck::cast_slice(data);
+        self.inner.enqueue_write_at(
+            queue.raw(),
+            range.start * size_of::<T>(),
+            bytes,
+            CL_BLOCKING,
+        )?;
+        Ok(self)
+    }
+
+    /// Non-blocking `write_range`; `data` stays borrowed by the guard until
+    /// the transfer has finished.
+    pub fn write_range_non_blocking<'a, P: ProfilingMode>(
+        mut self,
+        queue: &Queue<'brand, P>,
+        range: Range<usize>,
+        data: &'a [T],
+    ) -> Result<(
+        DeviceBuffer<'brand, T, InFlight>,
+        WriteGuard<'a, 'brand, T, P>,
+    )>
+    where
+        T: bytemuck::Pod,
+    {
+        check_host_range(&range, data.len(), self.len)?;
+
+        let bytes = bytemuck::cast_slice(data);
+        let evt = self.inner.enqueue_write_at(
+            queue.raw(),
+            range.start * size_of::<T>(),
+            bytes,
+            CL_NON_BLOCKING,
+        )?;
+
+        let guard = WriteGuard::new(data, EventToken::from_event(evt));
+        Ok((
+            DeviceBuffer::from_inner(self.inner.into_inflight(), self.len),
+            guard,
+        ))
+    }
 }
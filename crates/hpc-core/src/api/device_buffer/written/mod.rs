@@ -1,32 +1,9 @@
-// src/api/device_buffer/ready/mod.rs
-
-use crate::EventToken;
-use crate::api::{DeviceBuffer, Kernel, Queue};
-use crate::buffer::state::{InFlight, Written};
-use crate::error::Result;
+// src/api/device_buffer/written/mod.rs
+//
+// Kernel-Launches laufen über `Kernel::launch`, das alle Buffer-Argumente
+// nach InFlight bewegt (siehe kernel_args).
 
 // Import I/O implementations
+mod copy;
 mod io;
-
-//#####
-// COMPUTE OPERATIONS
-//#####
-
-impl<'brand, T> DeviceBuffer<'brand, T, Written> {
-    #[must_use = "GPU kernel started: you must wait for completion"]
-    pub fn enqueue_kernel(
-        self,
-        queue: &'brand Queue,
-        kernel: &Kernel<'brand>,
-        global_work_size: usize,
-    ) -> Result<(DeviceBuffer<'brand, T, InFlight>, EventToken<'brand>)> {
-        let (inner_inflight, evt) =
-            self.inner
-                .enqueue_kernel(queue.raw(), kernel.raw(), global_work_size)?;
-
-        Ok((
-            DeviceBuffer::from_inner(inner_inflight, self.len),
-            EventToken::from_event(evt),
-        ))
-    }
-}
+mod rect;
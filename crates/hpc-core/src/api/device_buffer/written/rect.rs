@@ -0,0 +1,65 @@
+//! Rectangular transfers for `DeviceBuffer<T, Written>`, see `BufferRect`.
+//!
+//! - `read_rect` / `write_rect`: blocking, between buffer and host slice;
+//!   the buffer stays `Written`.
+//! - `copy_rect_to`: device-side into another `Written` buffer; both move
+//!   to `InFlight` under one token like `copy_to`.
+
+use super::copy::CopyResult;
+use crate::EventToken;
+use crate::api::{BufferRect, DeviceBuffer, ProfilingMode, Queue};
+use crate::buffer::state::Written;
+use crate::error::Result;
+
+impl<'brand, T: bytemuck::Pod> DeviceBuffer<'brand, T, Written> {
+    /// Read the box `rect` of this buffer (source) into `out` (destination).
+    pub fn read_rect<P: ProfilingMode, const D: usize>(
+        &self,
+        queue: &Queue<'brand, P>,
+        rect: &BufferRect<D>,
+        out: &mut [T],
+    ) -> Result<()> {
+        rect.validate(self.len, out.len())?;
+        let bytes = rect.in_bytes(size_of::<T>());
+        self.inner
+            .read_rect(queue.raw(), &bytes, bytemuck::cast_slice_mut(out))
+    }
+
+    /// Write the box `rect` of `data` (source) into this buffer (destination).
+    pub fn write_rect<P: ProfilingMode, const D: usize>(
+        mut self,
+        queue: &Queue<'brand, P>,
+        rect: &BufferRect<D>,
+        data: &[T],
+    ) -> Result<DeviceBuffer<'brand, T, Written>> {
+        rect.validate(data.len(), self.len)?;
+        let bytes = rect.in_bytes(size_of::<T>());
+        self.inner
+            .write_rect(queue.raw(), &bytes, bytemuck::cast_slice(data))?;
+        Ok(self)
+    }
+
+    /// Copy the box `rect` of this buffer into `dst` on the device.
+    #[must_use = "GPU copy started: you must wait for completion"]
+    pub fn copy_rect_to<P: ProfilingMode, const D: usize>(
+        self,
+        queue: &Queue<'brand, P>,
+        dst: DeviceBuffer<'brand, T, Written>,
+        rect: &BufferRect<D>,
+    ) -> CopyResult<'brand, T, P> {
+        rect.validate(self.len, dst.len)?;
+        let (src_len, dst_len) = (self.len, dst.len);
+        let bytes = rect.in_bytes(size_of::<T>());
+        let (src_inflight, dst_inflight, evt) =
+            self.inner
+                .enqueue_copy_rect(queue.raw(), dst.inner, &bytes)?;
+
+        Ok((
+            (
+                DeviceBuffer::from_inner(src_inflight, src_len),
+                DeviceBuffer::from_inner(dst_inflight, dst_len),
+            ),
+            EventToken::from_event(evt),
+        ))
+    }
+}
@@ -1,15 +1,28 @@
 // src/api/mod.rs
 
 // Re-export von Error types
-pub use crate::error::{Error, Result};
+pub use crate::error::{BuildLog, Error, Result};
 
 // Submodule
 mod device_buffer;
 mod opencl;
 
 // Re-exports der Submodule
-pub use device_buffer::DeviceBuffer;
-pub use opencl::{Context, Kernel, Queue};
+pub(crate) use device_buffer::CompleteToken;
+pub use device_buffer::{DeviceBuffer, InFlightBuffers};
+pub(crate) use opencl::RectBytes;
+pub use opencl::vector;
+pub use opencl::{
+    AccessQualifier, AddressQualifier, ArgType, Buf, BufferRect, ClStd, CompiledProgram, Context,
+    ContextBuilder, Device, DeviceInfo, DeviceType, Kernel, KernelArg, KernelArgInfo, KernelArgs,
+    KernelScalar, KernelSignature, KernelWorkGroupInfo, LocalMem, NdRange, NoProfiling, Profiling,
+    ProfilingMode, Program, ProgramBuilder, Queue, QueueBuilder, TypeQualifiers, Untyped,
+};
+#[cfg(feature = "queue_hints")]
+pub use opencl::{QueuePriority, QueueThrottle};
+// Nur für #[derive(KernelArgs)], öffentlich über hpc_core::__private
+#[doc(hidden)]
+pub use opencl::ArgBinder;
 
 mod util;
 //pub use util::{EventToken, ReadGuard};
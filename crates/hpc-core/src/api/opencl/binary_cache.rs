@@ -0,0 +1,346 @@
+//! Opt-in on-disk cache of program binaries.
+//!
+//! Enabled per context (`ContextBuilder::binary_cache`,
+//! `Context::with_binary_cache`). `ProgramBuilder::build` stores the
+//! `CL_PROGRAM_BINARIES` of every device under a key hashed from the
+//! sources, embedded headers, build options, device name and driver
+//! version; later builds create the program with `clCreateProgramWithBinary`
+//! instead. Missing, stale or corrupt entries fall back to the source build.
+//! The contents of all files below `-I` directories are part of the key, so
+//! editing an included header misses the cache; if such a directory cannot
+//! be read, the program is built without cache.
+//!
+//! The file name is a 64-bit hash of the key; the full key is stored in
+//! the entry and compared on load, so a hash collision is a miss and never
+//! loads the binary of another program.
+//!
+//! Entries are written to a unique temporary file and renamed into place,
+//! so concurrent processes sharing a directory only ever read complete
+//! files. The cache is best effort: I/O errors never fail a build.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use opencl3::device::Device as CLDevice;
+use opencl3::types::cl_device_id;
+
+/// File header, bumped whenever the layout changes.
+const MAGIC: &[u8; 8] = b"HPCBIN02";
+
+/// Cache entries of one program, one per device.
+#[derive(Debug)]
+pub(crate) struct BinaryCache {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    device: cl_device_id,
+    /// Vollständiger Schlüssel, steht auch in der Datei
+    key: Vec<u8>,
+    path: PathBuf,
+}
+
+impl BinaryCache {
+    /// Compute the keys of a program built from `parts` for `devices`.
+    ///
+    /// `None` if a device cannot be queried; the program is then built
+    /// without cache.
+    pub(crate) fn new(dir: &Path, devices: &[cl_device_id], parts: &[&[u8]]) -> Option<Self> {
+        let entries = devices
+            .iter()
+            .map(|&id| {
+                let device = CLDevice::new(id);
+                let (name, driver) = (device.name().ok()?, device.driver_version().ok()?);
+                let key = key_material(parts, &name, &driver);
+                Some(Entry {
+                    device: id,
+                    path: entry_path(dir, &key),
+                    key,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            dir: dir.to_owned(),
+            entries,
+        })
+    }
+
+    /// Binaries of all devices, in the order passed to `new`; `None` if
+    /// any entry is missing or damaged.
+    pub(crate) fn load(&self) -> Option<Vec<Vec<u8>>> {
+        self.entries
+            .iter()
+            .map(|entry| read_entry(&entry.path, &entry.key))
+            .collect()
+    }
+
+    /// Store `(device, binary)` pairs; errors are ignored.
+    pub(crate) fn store(&self, binaries: &[(cl_device_id, Vec<u8>)]) {
+        for (device, binary) in binaries {
+            if binary.is_empty() {
+                continue;
+            }
+            if let Some(entry) = self.entries.iter().find(|e| e.device == *device) {
+                let _ = write_entry(&self.dir, &entry.path, &entry.key, binary);
+            }
+        }
+    }
+}
+
+/// Key of a program built from `parts` for one device: all parts
+/// length-prefixed, so `["ab", "c"]` and `["a", "bc"]` differ.
+fn key_material(parts: &[&[u8]], device: &str, driver: &str) -> Vec<u8> {
+    let mut key = Vec::new();
+    for part in parts
+        .iter()
+        .copied()
+        .chain([device.as_bytes(), driver.as_bytes()])
+    {
+        key.extend_from_slice(&(part.len() as u64).to_le_bytes());
+        key.extend_from_slice(part);
+    }
+    key
+}
+
+/// Cache file for `key`.
+fn entry_path(dir: &Path, key: &[u8]) -> PathBuf {
+    let mut hash = Fnv::new();
+    hash.write(key);
+    dir.join(format!("{:016x}.clbin", hash.finish()))
+}
+
+/// Relative path and contents of every file below `dirs`, as key parts.
+///
+/// `None` if a directory or file cannot be read.
+pub(crate) fn include_parts(dirs: &[PathBuf]) -> Option<Vec<Vec<u8>>> {
+    let mut parts = Vec::new();
+    for dir in dirs {
+        collect_files(dir, dir, &mut parts)?;
+    }
+    Some(parts)
+}
+
+// Sortiert, damit die Reihenfolge von read_dir den Schlüssel nicht ändert
+fn collect_files(root: &Path, dir: &Path, parts: &mut Vec<Vec<u8>>) -> Option<()> {
+    let mut paths = fs::read_dir(dir)
+        .ok()?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()
+        .ok()?;
+    paths.sort();
+
+    for path in paths {
+        // symlink_metadata: verlinkte Verzeichnisse nicht verfolgen (Zyklen)
+        if fs::symlink_metadata(&path).ok()?.is_dir() {
+            collect_files(root, &path, parts)?;
+        } else {
+            let name = path.strip_prefix(root).ok()?.as_os_str();
+            parts.push(name.as_encoded_bytes().to_vec());
+            parts.push(fs::read(&path).ok()?);
+        }
+    }
+    Some(())
+}
+
+//#####
+// FILES
+//#####
+
+// Layout: MAGIC | Schlüssellänge (u64 LE) | Schlüssel | FNV-1a des Inhalts (u64 LE) | Inhalt
+fn read_entry(path: &Path, key: &[u8]) -> Option<Vec<u8>> {
+    let data = fs::read(path).ok()?;
+    let rest = data.strip_prefix(MAGIC.as_slice())?;
+    let (key_len, rest) = rest.split_first_chunk::<8>()?;
+    let key_len = usize::try_from(u64::from_le_bytes(*key_len)).ok()?;
+    let (stored_key, rest) = rest.split_at_checked(key_len)?;
+    // Gleicher Dateiname, anderer Schlüssel: Hash-Kollision, kein Treffer
+    if stored_key != key {
+        return None;
+    }
+    let (checksum, binary) = rest.split_first_chunk::<8>()?;
+
+    let mut hash = Fnv::new();
+    hash.write(binary);
+    (u64::from_le_bytes(*checksum) == hash.finish() && !binary.is_empty()).then(|| binary.to_vec())
+}
+
+fn write_entry(dir: &Path, path: &Path, key: &[u8], binary: &[u8]) -> io::Result<()> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fs::create_dir_all(dir)?;
+    let tmp = dir.join(format!(
+        ".{}.{}-{}.tmp",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let mut hash = Fnv::new();
+    hash.write(binary);
+    let mut data = Vec::with_capacity(MAGIC.len() + 16 + key.len() + binary.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&(key.len() as u64).to_le_bytes());
+    data.extend_from_slice(key);
+    data.extend_from_slice(&hash.finish().to_le_bytes());
+    data.extend_from_slice(binary);
+
+    // rename ist atomar: Leser sehen die alte oder die neue Datei, nie halbe
+    let result = fs::write(&tmp, &data).and_then(|()| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+//#####
+// HASH
+//#####
+
+/// FNV-1a (64 bit); stable across Rust versions, unlike `DefaultHasher`.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frisches Verzeichnis pro Test unter dem System-Temp
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "hpc-core-binary-cache-{}-{name}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn fnv_is_stable() {
+        let hash = |bytes: &[u8]| {
+            let mut h = Fnv::new();
+            h.write(bytes);
+            h.finish()
+        };
+        // Referenzwerte von FNV-1a 64
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn key_depends_on_parts_and_device() {
+        let key = key_material(&[b"src", b"-O2"], "gpu", "1.0");
+        assert_eq!(key, key_material(&[b"src", b"-O2"], "gpu", "1.0"));
+        assert_ne!(key, key_material(&[b"src", b"-O3"], "gpu", "1.0"));
+        assert_ne!(key, key_material(&[b"src", b"-O2"], "gpu", "1.1"));
+        assert_ne!(key, key_material(&[b"src", b"-O2"], "cpu", "1.0"));
+        assert_ne!(
+            key_material(&[b"ab", b"c"], "gpu", "1.0"),
+            key_material(&[b"a", b"bc"], "gpu", "1.0")
+        );
+
+        let dir = Path::new("cache");
+        assert_eq!(entry_path(dir, &key), entry_path(dir, &key.clone()));
+        assert_ne!(
+            entry_path(dir, &key),
+            entry_path(dir, &key_material(&[b"src"], "gpu", "1.0"))
+        );
+    }
+
+    #[test]
+    fn corrupt_entries_are_misses() {
+        let dir = temp_dir("corrupt");
+        let path = dir.join("entry.clbin");
+        let key = key_material(&[b"src"], "gpu", "1.0");
+        assert_eq!(read_entry(&path, &key), None);
+
+        write_entry(&dir, &path, &key, b"binary").unwrap();
+        assert_eq!(read_entry(&path, &key).as_deref(), Some(&b"binary"[..]));
+
+        let data = fs::read(&path).unwrap();
+        let mut flipped = data.clone();
+        *flipped.last_mut().unwrap() ^= 1;
+        fs::write(&path, flipped).unwrap();
+        assert_eq!(read_entry(&path, &key), None);
+
+        fs::write(&path, &data[..MAGIC.len() + 4]).unwrap();
+        assert_eq!(read_entry(&path, &key), None);
+
+        fs::write(&path, &data[..data.len() - 7]).unwrap();
+        assert_eq!(read_entry(&path, &key), None);
+
+        let mut wrong_magic = data;
+        wrong_magic[0] ^= 1;
+        fs::write(&path, wrong_magic).unwrap();
+        assert_eq!(read_entry(&path, &key), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn colliding_key_is_a_miss() {
+        let dir = temp_dir("collision");
+        let path = dir.join("entry.clbin");
+        let key = key_material(&[b"kernel void a() {}"], "gpu", "1.0");
+        let other = key_material(&[b"kernel void b() {}"], "gpu", "1.0");
+
+        // Zweiter Schlüssel landet (wie bei einer Kollision) auf derselben Datei
+        write_entry(&dir, &path, &key, b"binary of a").unwrap();
+        assert_eq!(read_entry(&path, &other), None);
+        assert_eq!(
+            read_entry(&path, &key).as_deref(),
+            Some(&b"binary of a"[..])
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn changed_include_header_misses_cache() {
+        let dir = temp_dir("include");
+        let include = dir.join("include");
+        fs::create_dir_all(include.join("sub")).unwrap();
+        fs::write(include.join("common.h"), "#define N 16").unwrap();
+        fs::write(include.join("sub/util.h"), "int f(int);").unwrap();
+
+        let key = |include: &Path| {
+            let files = include_parts(&[include.to_owned()]).unwrap();
+            let mut parts: Vec<&[u8]> = vec![b"#include \"common.h\""];
+            parts.extend(files.iter().map(Vec::as_slice));
+            key_material(&parts, "gpu", "1.0")
+        };
+
+        let before = key(&include);
+        write_entry(&dir, &entry_path(&dir, &before), &before, b"binary").unwrap();
+        assert_eq!(key(&include), before);
+
+        fs::write(include.join("sub/util.h"), "int f(long);").unwrap();
+        let after = key(&include);
+        assert_ne!(entry_path(&dir, &after), entry_path(&dir, &before));
+        assert_eq!(read_entry(&entry_path(&dir, &after), &after), None);
+
+        assert_eq!(include_parts(&[dir.join("missing")]), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
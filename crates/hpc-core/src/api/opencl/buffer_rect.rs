@@ -0,0 +1,293 @@
+//! Rectangular (2D/3D strided) transfers (`clEnqueue{Read,Write,Copy}BufferRect`).
+//!
+//! `BufferRect<D>` with `D` = 2 or 3 describes a box of `region` elements
+//! moved between two row-major arrays. Source and destination each have
+//! their own origin, row pitch and (3D) slice pitch, all in elements; the
+//! pitches default to a tightly packed array of the region's size. Like
+//! in `NdRange`, origins of another dimension do not compile.
+//!
+//! ```ignore
+//! // 16x16 tile at (x, y) of an n x n matrix into a packed 16x16 host array
+//! let tile = BufferRect::d2(16, 16).src_origin([x, y]).src_row_pitch(n);
+//! matrix.read_rect(&queue, &tile, &mut host)?;
+//! ```
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferRect<const D: usize> {
+    region: [usize; D],
+    src: Layout<D>,
+    dst: Layout<D>,
+}
+
+/// Origin and pitches of one side; a pitch of 0 means tightly packed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Layout<const D: usize> {
+    origin: [usize; D],
+    row_pitch: usize,
+    slice_pitch: usize,
+}
+
+impl BufferRect<2> {
+    /// `width` elements per row, `height` rows.
+    pub fn d2(width: usize, height: usize) -> Self {
+        Self::from_region([width, height])
+    }
+}
+
+impl BufferRect<3> {
+    /// `width` elements per row, `height` rows, `depth` slices.
+    pub fn d3(width: usize, height: usize, depth: usize) -> Self {
+        Self::from_region([width, height, depth])
+    }
+
+    /// Distance between two slices of the source, in elements.
+    pub fn src_slice_pitch(mut self, pitch: usize) -> Self {
+        self.src.slice_pitch = pitch;
+        self
+    }
+
+    /// Distance between two slices of the destination, in elements.
+    pub fn dst_slice_pitch(mut self, pitch: usize) -> Self {
+        self.dst.slice_pitch = pitch;
+        self
+    }
+}
+
+impl<const D: usize> BufferRect<D> {
+    // Privat: nur D = 2, 3 über d2/d3
+    fn from_region(region: [usize; D]) -> Self {
+        let packed = Layout {
+            origin: [0; D],
+            row_pitch: 0,
+            slice_pitch: 0,
+        };
+        Self {
+            region,
+            src: packed,
+            dst: packed,
+        }
+    }
+
+    /// Position of the box in the source (x in elements, y in rows, z in slices).
+    pub fn src_origin(mut self, origin: [usize; D]) -> Self {
+        self.src.origin = origin;
+        self
+    }
+
+    /// Position of the box in the destination.
+    pub fn dst_origin(mut self, origin: [usize; D]) -> Self {
+        self.dst.origin = origin;
+        self
+    }
+
+    /// Elements per row of the whole source array.
+    pub fn src_row_pitch(mut self, pitch: usize) -> Self {
+        self.src.row_pitch = pitch;
+        self
+    }
+
+    /// Elements per row of the whole destination array.
+    pub fn dst_row_pitch(mut self, pitch: usize) -> Self {
+        self.dst.row_pitch = pitch;
+        self
+    }
+
+    pub fn region(&self) -> [usize; D] {
+        self.region
+    }
+
+    /// Number of elements moved.
+    pub fn elements(&self) -> usize {
+        self.region.iter().product()
+    }
+
+    /// Check the region against a source of `src_len` and a destination of
+    /// `dst_len` elements: `Error::InvalidRect` for an empty dimension or
+    /// bad pitches, `Error::OutOfBounds` if a side leaves its buffer.
+    pub(crate) fn validate(&self, src_len: usize, dst_len: usize) -> Result<()> {
+        if self.region.contains(&0) {
+            return Err(Error::InvalidRect(format!(
+                "rect region {:?} has an empty dimension",
+                self.region
+            )));
+        }
+        self.side(&self.src).validate("source", src_len)?;
+        self.side(&self.dst).validate("destination", dst_len)
+    }
+
+    /// Origins, region and pitches in the units OpenCL expects: x and all
+    /// pitches in bytes, y in rows, z in slices.
+    pub(crate) fn in_bytes(&self, elem: usize) -> RectBytes {
+        let (src, dst) = (self.side(&self.src), self.side(&self.dst));
+        let [w, h, d] = src.region;
+        RectBytes {
+            src_origin: [src.origin[0] * elem, src.origin[1], src.origin[2]],
+            dst_origin: [dst.origin[0] * elem, dst.origin[1], dst.origin[2]],
+            region: [w * elem, h, d],
+            src_row_pitch: src.row_pitch.saturating_mul(elem),
+            src_slice_pitch: src.slice_pitch.saturating_mul(elem),
+            dst_row_pitch: dst.row_pitch.saturating_mul(elem),
+            dst_slice_pitch: dst.slice_pitch.saturating_mul(elem),
+        }
+    }
+
+    /// One side in three dimensions with the default pitches filled in.
+    fn side(&self, layout: &Layout<D>) -> Side {
+        let mut region = [1; 3];
+        let mut origin = [0; 3];
+        region[..D].copy_from_slice(&self.region);
+        origin[..D].copy_from_slice(&layout.origin);
+
+        let row_pitch = match layout.row_pitch {
+            0 => region[0],
+            p => p,
+        };
+        let slice_pitch = match layout.slice_pitch {
+            0 => row_pitch.saturating_mul(region[1]),
+            p => p,
+        };
+        Side {
+            origin,
+            region,
+            row_pitch,
+            slice_pitch,
+        }
+    }
+}
+
+/// One side of a `BufferRect`, resolved to three dimensions.
+struct Side {
+    origin: [usize; 3],
+    region: [usize; 3],
+    row_pitch: usize,
+    slice_pitch: usize,
+}
+
+impl Side {
+    fn validate(&self, name: &str, len: usize) -> Result<()> {
+        let [w, h, d] = self.region;
+        if self.row_pitch < w {
+            return Err(Error::InvalidRect(format!(
+                "{name} row pitch {} is smaller than the region width {w}",
+                self.row_pitch
+            )));
+        }
+        if self.slice_pitch < self.row_pitch.saturating_mul(h)
+            || !self.slice_pitch.is_multiple_of(self.row_pitch)
+        {
+            return Err(Error::InvalidRect(format!(
+                "{name} slice pitch {} must be a multiple of the row pitch {} and cover {h} rows",
+                self.slice_pitch, self.row_pitch
+            )));
+        }
+
+        // Erstes und letztes berührtes Element, überlauffest
+        let offset = |[x, y, z]: [usize; 3]| {
+            y.checked_mul(self.row_pitch)
+                .zip(z.checked_mul(self.slice_pitch))
+                .and_then(|(y, z)| x.checked_add(y)?.checked_add(z))
+        };
+        let start = offset(self.origin);
+        let end = start
+            .zip(offset([w - 1, h - 1, d - 1]))
+            .and_then(|(start, last)| start.checked_add(last)?.checked_add(1));
+
+        match end {
+            Some(end) if end <= len => Ok(()),
+            _ => Err(Error::OutOfBounds {
+                offset: start.unwrap_or(usize::MAX),
+                len: end.zip(start).map_or(usize::MAX, |(e, s)| e - s),
+                buffer_len: len,
+            }),
+        }
+    }
+}
+
+/// `BufferRect` converted for the `clEnqueue*BufferRect` calls.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RectBytes {
+    pub(crate) src_origin: [usize; 3],
+    pub(crate) dst_origin: [usize; 3],
+    pub(crate) region: [usize; 3],
+    pub(crate) src_row_pitch: usize,
+    pub(crate) src_slice_pitch: usize,
+    pub(crate) dst_row_pitch: usize,
+    pub(crate) dst_slice_pitch: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_and_explicit_pitch() {
+        // 4x3 Kachel aus einer Zeile von 10 bei (2, 1) in ein gepacktes 4x3
+        let rect = BufferRect::d2(4, 3).src_origin([2, 1]).src_row_pitch(10);
+        assert!(rect.validate(10 * 4, 12).is_ok());
+        assert_eq!(rect.elements(), 12);
+
+        let bytes = rect.in_bytes(4);
+        assert_eq!(bytes.src_origin, [8, 1, 0]);
+        assert_eq!(bytes.dst_origin, [0, 0, 0]);
+        assert_eq!(bytes.region, [16, 3, 1]);
+        assert_eq!((bytes.src_row_pitch, bytes.src_slice_pitch), (40, 120));
+        assert_eq!((bytes.dst_row_pitch, bytes.dst_slice_pitch), (16, 48));
+
+        let cube = BufferRect::d3(2, 2, 2).dst_row_pitch(4).dst_slice_pitch(12);
+        assert!(cube.validate(8, 2 * 12).is_ok());
+        let bytes = cube.in_bytes(8);
+        assert_eq!((bytes.dst_row_pitch, bytes.dst_slice_pitch), (32, 96));
+    }
+
+    #[test]
+    fn rejects_bad_shapes() {
+        let invalid = |r: Result<()>| matches!(r, Err(Error::InvalidRect(_)));
+        assert!(invalid(BufferRect::d2(0, 3).validate(100, 100)));
+        assert!(invalid(
+            BufferRect::d2(8, 2).src_row_pitch(4).validate(100, 100)
+        ));
+        assert!(invalid(
+            BufferRect::d3(4, 4, 2)
+                .dst_slice_pitch(8)
+                .validate(100, 100)
+        ));
+        // Slice-Pitch kein Vielfaches des Row-Pitch
+        assert!(invalid(
+            BufferRect::d3(4, 2, 2)
+                .src_slice_pitch(10)
+                .validate(100, 100)
+        ));
+    }
+
+    #[test]
+    fn rejects_region_outside_buffer() {
+        let oob = |r: Result<()>| matches!(r, Err(Error::OutOfBounds { .. }));
+        let rect = BufferRect::d2(4, 3).src_origin([6, 1]).src_row_pitch(10);
+        // Letztes Element: (6 + 3) + (1 + 2) * 10 = 39
+        assert!(rect.validate(40, 12).is_ok());
+        assert!(oob(rect.validate(39, 12)));
+        assert!(oob(rect.validate(40, 11)));
+    }
+
+    #[test]
+    fn overflow_is_out_of_bounds() {
+        let rect = BufferRect::d2(4, 2)
+            .src_origin([0, usize::MAX / 2])
+            .src_row_pitch(4);
+        assert!(matches!(
+            rect.validate(usize::MAX, 8),
+            Err(Error::OutOfBounds {
+                offset: usize::MAX,
+                ..
+            })
+        ));
+
+        let huge = BufferRect::d2(usize::MAX, 2);
+        assert!(matches!(
+            huge.validate(usize::MAX, usize::MAX),
+            Err(Error::OutOfBounds { .. })
+        ));
+    }
+}
@@ -1,18 +1,17 @@
 // src/api/context.rs
 
 use opencl3::{
-    command_queue::CommandQueue as CLQueue,
     context::Context as CLContext,
-    device::{CL_DEVICE_TYPE_GPU, get_all_devices},
-    platform::get_platforms,
     types::{cl_context_properties, cl_device_id},
 };
 
+use super::{ContextBuilder, Device, DeviceInfo, ProgramBuilder, QueueBuilder};
 use crate::api::DeviceBuffer;
 use crate::api::Queue;
 use crate::buffer::state::Empty;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
 use std::ptr;
 
 //#####
@@ -23,36 +22,107 @@ use std::ptr;
 #[derive(Debug)]
 pub struct Context<'brand> {
     inner: CLContext,
-    device: cl_device_id,
+    /// All devices of the CL context; `devices[0]` is the primary device.
+    devices: Vec<cl_device_id>,
+    /// Directory of the program binary cache, if enabled.
+    binary_cache: Option<PathBuf>,
     _brand: PhantomData<fn(&'brand ()) -> &'brand ()>,
 }
 
 impl<'brand> Context<'brand> {
-    /// Build an OpenCL context for the first found GPU device + matching queue.
-    /*
-    possible error: Assumption: 1 platform with 1 device -> NVIDIA
-    */
+    /// Build an OpenCL context for the first GPU device found on any platform.
+    ///
+    /// Use `Context::builder()` to select CPU devices, a specific platform or
+    /// a device other than the first one.
     pub fn create_context() -> Result<Self> {
-        let _platforms = get_platforms()?; // aktuell ungenutzt, später ggf. filtern
-        let devices: Vec<cl_device_id> = get_all_devices(CL_DEVICE_TYPE_GPU)?;
+        ContextBuilder::new().build()
+    }
+
+    /// Start a `ContextBuilder` for filtered device selection.
+    pub fn builder() -> ContextBuilder {
+        ContextBuilder::new()
+    }
+
+    /// Run `f` with a context on the first GPU whose brand is unique.
+    ///
+    /// `create_context` lets the caller pick `'brand`, so two contexts in
+    /// the same scope can unify to one brand. Here the brand is
+    /// introduced by the `for<'new>` bound: it differs from every other
+    /// brand and cannot escape through `R`, so buffers, kernels and queues
+    /// of different contexts can never be mixed.
+    pub fn with<R>(f: impl for<'new> FnOnce(&'new Context<'new>) -> R) -> Result<R> {
+        ContextBuilder::new().with(f)
+    }
+
+    /// Create a context containing `devices` (all from the same platform).
+    pub(crate) fn from_devices(devices: Vec<cl_device_id>) -> Result<Self> {
         let props: &[cl_context_properties] = &[];
         let ctx = CLContext::from_devices(&devices, props, None, ptr::null_mut())?;
 
         Ok(Self {
             inner: ctx,
-            device: devices[0],
+            devices,
+            binary_cache: None,
             _brand: PhantomData,
         })
     }
 
-    pub fn create_queue(&'brand self) -> Result<Queue<'brand>> {
-        let q = CLQueue::create(&self.inner, self.device, 0)?;
-        Ok(Queue {
-            inner: q,
+    /// Wrap an existing opencl3 context, e.g. one created for CL/GL interop.
+    ///
+    /// `primary` must be one of the context's devices; it becomes the
+    /// device used by `create_queue`.
+    pub fn from_raw(inner: CLContext, primary: cl_device_id) -> Result<Self> {
+        let mut devices = inner.devices().to_vec();
+        let pos = devices.iter().position(|&d| d == primary).ok_or_else(|| {
+            Error::HandleMismatch("primary device is not part of the given context".into())
+        })?;
+        devices.remove(pos);
+        devices.insert(0, primary);
+
+        Ok(Self {
+            inner,
+            devices,
+            binary_cache: None,
             _brand: PhantomData,
         })
     }
 
+    /// Create an in-order queue without profiling on the primary device.
+    pub fn create_queue(&'brand self) -> Result<Queue<'brand>> {
+        self.queue_builder().build()
+    }
+
+    /// Create a queue bound to `device`.
+    ///
+    /// Buffers of this context can be used with queues of every device
+    /// of the context; the brand stays the same.
+    pub fn create_queue_for(&'brand self, device: Device<'brand>) -> Result<Queue<'brand>> {
+        self.queue_builder().device(device).build()
+    }
+
+    /// Start a `QueueBuilder` for queues with profiling, out-of-order
+    /// execution or other properties.
+    pub fn queue_builder(&'brand self) -> QueueBuilder<'brand> {
+        QueueBuilder::new(self)
+    }
+
+    /// Cache program binaries in `dir` (see `ContextBuilder::binary_cache`).
+    pub fn with_binary_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.binary_cache = Some(dir.into());
+        self
+    }
+
+    /// Directory of the program binary cache, if enabled.
+    pub fn binary_cache(&self) -> Option<&Path> {
+        self.binary_cache.as_deref()
+    }
+
+    /// Start a `ProgramBuilder` for programs with build options, several
+    /// sources or separate compile/link.
+    pub fn program_builder(&'brand self) -> ProgramBuilder<'brand> {
+        ProgramBuilder::new(self)
+    }
+
     pub fn create_empty_buffer<T>(
         &'brand self,
         n_elems: usize,
@@ -69,7 +139,23 @@ impl<'brand> Context<'brand> {
         &self.inner
     }
 
+    /// Id of the primary device.
     pub fn device_id(&self) -> cl_device_id {
-        self.device
+        self.devices[0]
+    }
+
+    /// Query the capabilities of the primary device (`clGetDeviceInfo`).
+    pub fn device_info(&self) -> Result<DeviceInfo> {
+        DeviceInfo::query(self.device_id())
+    }
+
+    /// The device used by `create_queue`.
+    pub fn primary_device(&self) -> Device<'brand> {
+        Device::new(self.devices[0])
+    }
+
+    /// All devices of this context, primary device first.
+    pub fn devices(&self) -> Vec<Device<'brand>> {
+        self.devices.iter().map(|&id| Device::new(id)).collect()
     }
 }
@@ -0,0 +1,261 @@
+//! Device and platform selection for `Context`.
+//!
+//! `ContextBuilder` walks all OpenCL platforms, filters their devices by
+//! the configured criteria and creates the context for the selected one
+//! (or, with `all_devices`, for every matching device of its platform).
+//! If nothing matches, `build` returns `Error::DeviceNotFound` describing
+//! the active filters instead of panicking on an empty device list.
+
+use opencl3::{
+    device::{
+        CL_DEVICE_TYPE_ACCELERATOR, CL_DEVICE_TYPE_ALL, CL_DEVICE_TYPE_CPU, CL_DEVICE_TYPE_GPU,
+        Device,
+    },
+    error_codes::CL_DEVICE_NOT_FOUND,
+    platform::get_platforms,
+    types::{cl_device_id, cl_device_type, cl_platform_id},
+};
+
+use std::path::PathBuf;
+
+use super::Context;
+use crate::error::{Error, Result};
+
+//#####
+// DEVICE TYPE
+//#####
+
+/// Kind of OpenCL device to select.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeviceType {
+    Cpu,
+    #[default]
+    Gpu,
+    Accelerator,
+    /// Any device type, in platform enumeration order.
+    All,
+}
+
+impl DeviceType {
+    pub(crate) fn raw(self) -> cl_device_type {
+        match self {
+            DeviceType::Cpu => CL_DEVICE_TYPE_CPU,
+            DeviceType::Gpu => CL_DEVICE_TYPE_GPU,
+            DeviceType::Accelerator => CL_DEVICE_TYPE_ACCELERATOR,
+            DeviceType::All => CL_DEVICE_TYPE_ALL,
+        }
+    }
+}
+
+//#####
+// BUILDER
+//#####
+
+/// Builder for a `Context` on a filtered device.
+///
+/// Without any filter the builder selects the first GPU, which is what
+/// `Context::create_context` does.
+#[derive(Debug, Clone, Default)]
+pub struct ContextBuilder {
+    platform_name: Option<String>,
+    platform_vendor: Option<String>,
+    device_type: DeviceType,
+    device_index: usize,
+    min_version: Option<(u32, u32)>,
+    extensions: Vec<String>,
+    all_devices: bool,
+    binary_cache: Option<PathBuf>,
+}
+
+impl ContextBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only consider platforms whose name contains `name` (case-insensitive).
+    pub fn platform_name(mut self, name: impl Into<String>) -> Self {
+        self.platform_name = Some(name.into());
+        self
+    }
+
+    /// Only consider platforms whose vendor contains `vendor` (case-insensitive).
+    pub fn platform_vendor(mut self, vendor: impl Into<String>) -> Self {
+        self.platform_vendor = Some(vendor.into());
+        self
+    }
+
+    pub fn device_type(mut self, device_type: DeviceType) -> Self {
+        self.device_type = device_type;
+        self
+    }
+
+    /// Pick the n-th matching device (across all matching platforms).
+    pub fn device_index(mut self, index: usize) -> Self {
+        self.device_index = index;
+        self
+    }
+
+    /// Require at least OpenCL `major.minor` as reported by `CL_DEVICE_VERSION`.
+    pub fn min_opencl_version(mut self, major: u32, minor: u32) -> Self {
+        self.min_version = Some((major, minor));
+        self
+    }
+
+    /// Require a device extension, e.g. `cl_khr_fp64`. Can be called repeatedly.
+    pub fn require_extension(mut self, extension: impl Into<String>) -> Self {
+        self.extensions.push(extension.into());
+        self
+    }
+
+    /// Also put every other matching device of the selected device's
+    /// platform into the context. The selected device becomes the primary
+    /// device (`Context::create_queue`), the others are reachable through
+    /// `Context::devices` and `Context::create_queue_for`.
+    pub fn all_devices(mut self) -> Self {
+        self.all_devices = true;
+        self
+    }
+
+    /// Cache program binaries in `dir`, keyed by source, options, device
+    /// and driver version. Programs built on this context load their
+    /// binaries from there on later runs instead of compiling again.
+    pub fn binary_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.binary_cache = Some(dir.into());
+        self
+    }
+
+    /// Select the device(s) and create the context.
+    pub fn build<'brand>(self) -> Result<Context<'brand>> {
+        let candidates = self.matching_devices()?;
+        let (platform, primary) = candidates
+            .get(self.device_index)
+            .copied()
+            .ok_or_else(|| Error::DeviceNotFound(self.describe(candidates.len())))?;
+
+        let mut devices = vec![primary];
+        if self.all_devices {
+            // A CL context cannot span platforms
+            devices.extend(
+                candidates
+                    .iter()
+                    .filter(|&&(p, d)| p == platform && d != primary)
+                    .map(|&(_, d)| d),
+            );
+        }
+
+        let ctx = Context::from_devices(devices)?;
+        Ok(match self.binary_cache {
+            Some(dir) => ctx.with_binary_cache(dir),
+            None => ctx,
+        })
+    }
+
+    /// Build the context and run `f` with a unique brand, see `Context::with`.
+    pub fn with<R>(self, f: impl for<'new> FnOnce(&'new Context<'new>) -> R) -> Result<R> {
+        let ctx = self.build()?;
+        Ok(f(&ctx))
+    }
+
+    /// All devices matching the filters, in platform enumeration order.
+    fn matching_devices(&self) -> Result<Vec<(cl_platform_id, cl_device_id)>> {
+        let mut candidates = Vec::new();
+
+        for platform in get_platforms()? {
+            if !matches_filter(self.platform_name.as_deref(), &platform.name()?)
+                || !matches_filter(self.platform_vendor.as_deref(), &platform.vendor()?)
+            {
+                continue;
+            }
+
+            // CL_DEVICE_NOT_FOUND only means "none of this type on this platform"
+            let ids = match platform.get_devices(self.device_type.raw()) {
+                Ok(ids) => ids,
+                Err(e) if e.0 == CL_DEVICE_NOT_FOUND => continue,
+                Err(e) => return Err(e.into()),
+            };
+
+            for id in ids {
+                if self.device_matches(&Device::new(id))? {
+                    candidates.push((platform.id(), id));
+                }
+            }
+        }
+
+        Ok(candidates)
+    }
+
+    fn device_matches(&self, device: &Device) -> Result<bool> {
+        if let Some(min) = self.min_version {
+            match parse_opencl_version(&device.version()?) {
+                Some(version) if version >= min => {}
+                _ => return Ok(false),
+            }
+        }
+
+        if !self.extensions.is_empty() {
+            let available = device.extensions()?;
+            let available: Vec<&str> = available.split_whitespace().collect();
+            if !self
+                .extensions
+                .iter()
+                .all(|ext| available.contains(&ext.as_str()))
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn describe(&self, n_matching: usize) -> String {
+        let mut msg = format!("device type {:?}", self.device_type);
+        if let Some(name) = &self.platform_name {
+            msg.push_str(&format!(", platform name ~ {name:?}"));
+        }
+        if let Some(vendor) = &self.platform_vendor {
+            msg.push_str(&format!(", platform vendor ~ {vendor:?}"));
+        }
+        if let Some((major, minor)) = self.min_version {
+            msg.push_str(&format!(", OpenCL >= {major}.{minor}"));
+        }
+        if !self.extensions.is_empty() {
+            msg.push_str(&format!(", extensions {:?}", self.extensions));
+        }
+        format!(
+            "no device matches [{msg}] at index {} ({n_matching} matching)",
+            self.device_index
+        )
+    }
+}
+
+fn matches_filter(filter: Option<&str>, value: &str) -> bool {
+    filter.is_none_or(|f| value.to_lowercase().contains(&f.to_lowercase()))
+}
+
+/// Parses `"OpenCL <major>.<minor> <vendor info>"` (CL_DEVICE_VERSION format).
+pub(crate) fn parse_opencl_version(version: &str) -> Option<(u32, u32)> {
+    let number = version.strip_prefix("OpenCL ")?.split_whitespace().next()?;
+    let (major, minor) = number.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_opencl_version;
+
+    #[test]
+    fn parses_device_version() {
+        assert_eq!(parse_opencl_version("OpenCL 3.0 CUDA"), Some((3, 0)));
+        assert_eq!(parse_opencl_version("OpenCL 1.2 pocl 5.0"), Some((1, 2)));
+        assert_eq!(parse_opencl_version("OpenCL 2.1"), Some((2, 1)));
+    }
+
+    #[test]
+    fn rejects_malformed_version() {
+        assert_eq!(parse_opencl_version(""), None);
+        assert_eq!(parse_opencl_version("OpenCL C 1.2"), None);
+        assert_eq!(parse_opencl_version("OpenCL 3"), None);
+        assert_eq!(parse_opencl_version("OpenCL x.y"), None);
+        assert_eq!(parse_opencl_version("3.0 OpenCL"), None);
+    }
+}
@@ -0,0 +1,35 @@
+//! Branded device handle.
+//!
+//! A `Device<'brand>` can only be obtained from `Context::devices()`, so a
+//! queue created with `Context::create_queue_for` is always bound to a
+//! device of that very context.
+
+use opencl3::types::cl_device_id;
+use std::marker::PhantomData;
+
+use super::DeviceInfo;
+use crate::error::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Device<'brand> {
+    id: cl_device_id,
+    _brand: PhantomData<fn(&'brand ()) -> &'brand ()>,
+}
+
+impl<'brand> Device<'brand> {
+    pub(crate) fn new(id: cl_device_id) -> Self {
+        Self {
+            id,
+            _brand: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> cl_device_id {
+        self.id
+    }
+
+    /// Query the capabilities of this device (`clGetDeviceInfo`).
+    pub fn info(&self) -> Result<DeviceInfo> {
+        DeviceInfo::query(self.id)
+    }
+}
@@ -0,0 +1,77 @@
+//! Typed device capability query.
+//!
+//! `DeviceInfo` is a plain snapshot of the `clGetDeviceInfo` values needed
+//! to size work-groups and buffers. It is `Serialize` so benchmark runs can
+//! log the hardware they ran on.
+
+use opencl3::{device::Device, types::cl_device_id};
+use serde::Serialize;
+
+use crate::error::Result;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeviceInfo {
+    pub name: String,
+    pub vendor: String,
+    pub driver_version: String,
+    /// `CL_DEVICE_OPENCL_C_VERSION`, e.g. `"OpenCL C 1.2 "`.
+    pub opencl_c_version: String,
+    pub compute_units: u32,
+    pub max_work_group_size: usize,
+    /// Per-dimension limits; the length is `CL_DEVICE_MAX_WORK_ITEM_DIMENSIONS`.
+    pub max_work_item_sizes: Vec<usize>,
+    pub global_mem_size: u64,
+    pub local_mem_size: u64,
+    pub max_constant_buffer_size: u64,
+    pub max_mem_alloc_size: u64,
+    pub image_support: bool,
+    pub double_support: bool,
+    pub half_support: bool,
+    pub extensions: Vec<String>,
+}
+
+impl DeviceInfo {
+    /// Query all fields for `device`.
+    pub(crate) fn query(device: cl_device_id) -> Result<Self> {
+        let dev = Device::new(device);
+
+        let extensions: Vec<String> = dev
+            .extensions()?
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect();
+
+        // fp64/fp16 are optional features; the config queries may fail on
+        // devices that do not support them, so treat errors as "unsupported".
+        let double_support = extensions.iter().any(|e| e == "cl_khr_fp64")
+            || dev.double_fp_config().is_ok_and(|cfg| cfg != 0);
+        let half_support = extensions.iter().any(|e| e == "cl_khr_fp16");
+
+        Ok(Self {
+            name: dev.name()?,
+            vendor: dev.vendor()?,
+            driver_version: dev.driver_version()?,
+            opencl_c_version: dev.opencl_c_version()?,
+            compute_units: dev.max_compute_units()?,
+            max_work_group_size: dev.max_work_group_size()?,
+            max_work_item_sizes: dev.max_work_item_sizes()?,
+            global_mem_size: dev.global_mem_size()?,
+            local_mem_size: dev.local_mem_size()?,
+            max_constant_buffer_size: dev.max_constant_buffer_size()?,
+            max_mem_alloc_size: dev.max_mem_alloc_size()?,
+            image_support: dev.image_support()?,
+            double_support,
+            half_support,
+            extensions,
+        })
+    }
+
+    /// Number of work-item dimensions supported by the device.
+    pub fn max_work_item_dimensions(&self) -> usize {
+        self.max_work_item_sizes.len()
+    }
+
+    pub fn has_extension(&self, name: &str) -> bool {
+        self.extensions.iter().any(|e| e == name)
+    }
+}
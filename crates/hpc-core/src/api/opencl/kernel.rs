@@ -1,61 +1,242 @@
 //! High-level wrapper for OpenCL kernels.
 //! Provides a safe API around cl_kernel handles, bound to a specific
-//! - Lifetime branding (`'q`) to prevent cross-queue mixing.
+//! - Lifetime branding (`'brand`) to prevent cross-context mixing.
+//! - `launch` binds a complete argument list and moves every buffer
+//!   argument to `InFlight` (see `kernel_args`).
+//! - Launch over a 1D/2D/3D `NdRange` with optional local size and offset.
+//! - Optional typed signature (`Kernel<'brand, (Buf<f32>, u32)>`, see
+//!   `signature`) fixing the argument types and order at compile time.
 //!
-use opencl3::{kernel::Kernel as CLKernel, program::Program as CLProgram};
+use opencl3::kernel::Kernel as CLKernel;
+use std::sync::Arc;
 
-use super::Context;
-use crate::DeviceBuffer;
-use crate::buffer::state::Written;
-use crate::error::Result;
+use super::kernel_args::{ArgBinder, KernelArgs};
+use super::program::RawProgram;
+use super::signature::{KernelSignature, Untyped};
+use super::{
+    Context, Device, KernelArgInfo, KernelWorkGroupInfo, NdRange, ProfilingMode, Program, Queue,
+};
+use crate::api::EventToken;
+use crate::error::{Error, Result};
 use std::marker::PhantomData;
 
 #[must_use]
 #[derive(Debug)]
-pub struct Kernel<'brand> {
+pub struct Kernel<'brand, S = Untyped> {
     inner: CLKernel,
+    name: String,
+    num_args: u32,
+    /// Signatur gegen clGetKernelArgInfo geprüft (nicht nur die Anzahl)
+    verified: bool,
+    /// Hält das Programm am Leben, solange der Kernel existiert
     #[allow(dead_code)]
-    program: CLProgram,
-    _brand: PhantomData<&'brand ()>,
+    program: Arc<RawProgram>,
+    _brand: PhantomData<fn(&'brand ()) -> &'brand ()>,
+    _sig: PhantomData<fn() -> S>,
 }
 
 impl<'brand> Kernel<'brand> {
+    /// Build `src` and create the kernel `name`.
+    ///
+    /// Compiles `src` on every call; for several kernels of one source use
+    /// `Program::from_source` and `Program::kernel`. A failed build returns
+    /// `Error::Build` with the compiler log.
     pub fn from_source(ctx: &'brand Context<'brand>, src: &str, name: &str) -> Result<Self> {
-        let program = CLProgram::create_and_build_from_source(ctx.raw(), src, "")?;
-        let inner = CLKernel::create(&program, name)?;
+        Program::from_source(ctx, src)?.kernel(name)
+    }
+
+    pub(crate) fn new(inner: CLKernel, name: &str, program: Arc<RawProgram>) -> Result<Self> {
+        let num_args = inner.num_args()?;
         Ok(Self {
             inner,
+            name: name.to_owned(),
+            num_args,
+            verified: false,
             program,
             _brand: PhantomData,
+            _sig: PhantomData,
+        })
+    }
+
+    /// Attach the signature `S`, e.g. `kernel.typed::<(Buf<f32>, Buf<f32>, u32)>()`.
+    ///
+    /// Fails with `Error::KernelArgs` if the parameter count differs or, when
+    /// the driver reports argument info, a parameter has a different address
+    /// space or type name.
+    ///
+    /// Argument info is optional: programs from binaries, or built without
+    /// `-cl-kernel-arg-info` on some drivers, do not provide it. Then only
+    /// the count is checked and `is_verified` on the result returns `false`.
+    pub fn typed<S: KernelSignature>(self) -> Result<Kernel<'brand, S>> {
+        let mismatch = |msg: String| Error::KernelArgs {
+            kernel: self.name.clone(),
+            msg,
+        };
+
+        if S::LEN != self.num_args {
+            return Err(mismatch(format!(
+                "signature has {} arguments, kernel has {}",
+                S::LEN,
+                self.num_args
+            )));
+        }
+
+        // Driver without arg info: only the count is checked
+        let infos = self.arg_infos()?;
+        if let Some(infos) = &infos {
+            S::verify(infos).map_err(mismatch)?;
+        }
+
+        Ok(Kernel {
+            inner: self.inner,
+            name: self.name,
+            num_args: self.num_args,
+            verified: infos.is_some(),
+            program: self.program,
+            _brand: PhantomData,
+            _sig: PhantomData,
         })
     }
 
+    /// Bind `args` to all kernel parameters in order and enqueue the kernel
+    /// over `range` (a `usize` for 1D or an `NdRange`).
+    ///
+    /// Every buffer in `args` is moved to `InFlight`; the returned token's
+    /// `wait` gives all of them back as `Written`. `args` must cover every
+    /// parameter of the kernel, otherwise `Error::KernelArgs` is returned.
+    ///
+    /// ```ignore
+    /// let ((a, b, out, ()), evt) = kernel.launch(&queue, (a, b, out, n as u32), n)?;
+    /// let (a, b, out, ()) = evt.wait((a, b, out, ()))?;
+    /// ```
+    #[must_use = "GPU kernel started: you must wait for completion"]
+    pub fn launch<A: KernelArgs<'brand>, P: ProfilingMode, const D: usize>(
+        &self,
+        queue: &Queue<'brand, P>,
+        args: A,
+        range: impl Into<NdRange<D>>,
+    ) -> Result<(A::InFlight, EventToken<'brand, P>)> {
+        self.launch_args(queue, args, range.into())
+    }
+}
+
+impl<'brand, S: KernelSignature> Kernel<'brand, S> {
+    /// `true` if `typed` compared `S` with the driver's argument info,
+    /// `false` if only the parameter count could be checked.
+    pub fn is_verified(&self) -> bool {
+        self.verified
+    }
+
+    /// Launch with exactly the arguments of the signature, in order.
+    ///
+    /// Like the untyped `launch`, every buffer moves to `InFlight`.
+    #[must_use = "GPU kernel started: you must wait for completion"]
+    pub fn launch<P: ProfilingMode, const D: usize>(
+        &self,
+        queue: &Queue<'brand, P>,
+        args: S::Args<'brand>,
+        range: impl Into<NdRange<D>>,
+    ) -> Result<(
+        <S::Args<'brand> as KernelArgs<'brand>>::InFlight,
+        EventToken<'brand, P>,
+    )> {
+        self.launch_args(queue, args, range.into())
+    }
+}
+
+impl<'brand, S> Kernel<'brand, S> {
     pub fn raw(&self) -> &CLKernel {
         &self.inner
     }
 
-    /// Set a buffer argument (only ready buffers with the same brand are allowed)
-    pub fn set_arg_buffer<T>(
-        &self,
-        index: u32,
-        buf: &DeviceBuffer<'brand, T, Written>,
-    ) -> Result<()> {
-        self.inner.set_arg(index, buf.inner.raw())?;
-        Ok(())
+    /// Kernel function name as passed to `from_source`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Number of kernel parameters (`CL_KERNEL_NUM_ARGS`).
+    pub fn num_args(&self) -> u32 {
+        self.num_args
+    }
+
+    /// Metadata of parameter `index`; `None` if the driver does not provide it.
+    pub fn arg_info(&self, index: u32) -> Result<Option<KernelArgInfo>> {
+        KernelArgInfo::query(&self.inner, index)
+    }
+
+    /// Metadata of all parameters; `None` if the driver does not provide it.
+    pub fn arg_infos(&self) -> Result<Option<Vec<KernelArgInfo>>> {
+        (0..self.num_args)
+            .map(|index| self.arg_info(index))
+            .collect()
+    }
+
+    /// Work-group limits and memory usage of this kernel on `device`
+    /// (`clGetKernelWorkGroupInfo`).
+    pub fn work_group_info(&self, device: Device<'brand>) -> Result<KernelWorkGroupInfo> {
+        KernelWorkGroupInfo::query(&self.inner, device.id())
     }
 
-    /// Scalar argument: only allowed POD types
-    pub fn set_arg_scalar<S: KernelScalar>(&self, index: u32, val: &S) -> Result<()> {
-        self.inner.set_arg(index, val)?; // unsafe entfernt - nicht nötig
-        Ok(())
+    fn launch_args<A: KernelArgs<'brand>, P: ProfilingMode, const D: usize>(
+        &self,
+        queue: &Queue<'brand, P>,
+        args: A,
+        range: NdRange<D>,
+    ) -> Result<(A::InFlight, EventToken<'brand, P>)> {
+        let mut binder = ArgBinder::new(&self.inner, &self.name, queue.device);
+        let inflight = args.bind(&mut binder)?;
+        if binder.index() != self.num_args {
+            return Err(Error::KernelArgs {
+                kernel: self.name.clone(),
+                msg: format!(
+                    "{} arguments given, kernel has {}",
+                    binder.index(),
+                    self.num_args
+                ),
+            });
+        }
+        binder.finish()?;
+
+        let evt = range.enqueue(queue.raw(), &self.inner)?;
+        Ok((inflight, EventToken::from_event(evt)))
     }
 }
 
 /// Marker trait for allowed scalars (no &Vec/&[T]/Pointer etc.)
-pub trait KernelScalar: bytemuck::Pod {}
-impl KernelScalar for u8 {}
-impl KernelScalar for i32 {}
-impl KernelScalar for u32 {}
-impl KernelScalar for f32 {}
-impl KernelScalar for f64 {}
-impl KernelScalar for u64 {}
+pub trait KernelScalar: bytemuck::Pod {
+    /// OpenCL C type name (e.g. `"uint"`) used to verify typed kernels;
+    /// `None` skips the type-name check.
+    const CL_TYPE: Option<&'static str> = None;
+
+    /// Alignment of the type in OpenCL C. Built-in scalars and vectors are
+    /// aligned to their size there, which not every host ABI does (e.g.
+    /// `u64` on 32-bit x86); `#[derive(KernelScalar)]` checks its fields
+    /// against this.
+    const DEVICE_ALIGN: usize = align_of::<Self>();
+}
+
+macro_rules! impl_kernel_scalar {
+    ($($ty:ty => $cl:literal),+ $(,)?) => {
+        $(impl KernelScalar for $ty {
+            const CL_TYPE: Option<&'static str> = Some($cl);
+            const DEVICE_ALIGN: usize = size_of::<$ty>();
+        })+
+    };
+}
+
+impl_kernel_scalar!(
+    i8 => "char",
+    u8 => "uchar",
+    i16 => "short",
+    u16 => "ushort",
+    i32 => "int",
+    u32 => "uint",
+    i64 => "long",
+    u64 => "ulong",
+    f32 => "float",
+    f64 => "double",
+);
+
+// Braucht cl_khr_fp16 auf dem Device
+#[cfg(feature = "half")]
+impl_kernel_scalar!(half::f16 => "half");
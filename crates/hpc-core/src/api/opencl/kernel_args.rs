@@ -0,0 +1,267 @@
+//! Kernel arguments bound in declaration order.
+//!
+//! `KernelArg` is one argument (a `Written` buffer, a `KernelScalar` or a
+//! `LocalMem`),
+//! `KernelArgs` a complete argument list (tuples, or structs via
+//! `#[derive(KernelArgs)]`). `Kernel::launch` binds the list starting at
+//! index 0 and moves every buffer in it to `InFlight`, so no buffer the
+//! kernel uses can be touched on the host before the launch is waited on.
+//!
+//! Both traits are sealed: binding consumes the argument, and only the
+//! implementations here can reach `ArgBinder`. A borrowed buffer can
+//! therefore never be bound while staying usable on the host.
+
+use opencl3::device::Device as CLDevice;
+use opencl3::kernel::Kernel as CLKernel;
+use opencl3::types::cl_device_id;
+use std::marker::PhantomData;
+
+use super::{KernelScalar, LocalMem};
+use crate::__private::ArgsSealed;
+use crate::api::{DeviceBuffer, InFlightBuffers};
+use crate::buffer::state::{InFlight, Written};
+use crate::error::{Error, Result, ResultExt};
+
+//#####
+// BINDER
+//#####
+
+/// Sets kernel arguments at consecutive indices.
+///
+/// Only created by `Kernel::launch` and only usable inside this crate; the
+/// index advances with every bound argument, so arguments cannot be
+/// skipped or bound twice.
+pub struct ArgBinder<'k, 'brand> {
+    kernel: &'k CLKernel,
+    name: &'k str,
+    device: cl_device_id,
+    index: u32,
+    /// Sum of all `__local` arguments bound so far.
+    local_bytes: u64,
+    /// `CL_DEVICE_LOCAL_MEM_SIZE`, erst beim ersten `local` abgefragt
+    local_mem_limit: Option<u64>,
+    _brand: PhantomData<fn(&'brand ()) -> &'brand ()>,
+}
+
+impl<'k, 'brand> ArgBinder<'k, 'brand> {
+    pub(crate) fn new(kernel: &'k CLKernel, name: &'k str, device: cl_device_id) -> Self {
+        Self {
+            kernel,
+            name,
+            device,
+            index: 0,
+            local_bytes: 0,
+            local_mem_limit: None,
+            _brand: PhantomData,
+        }
+    }
+
+    /// Index the next argument will be bound to (= number bound so far).
+    pub(crate) fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Bind a `__global` buffer argument; the buffer moves to `InFlight`.
+    pub(crate) fn buffer<T>(
+        &mut self,
+        buf: DeviceBuffer<'brand, T, Written>,
+    ) -> Result<DeviceBuffer<'brand, T, InFlight>> {
+        let index = self.index;
+        self.kernel
+            .set_arg(index, buf.inner.raw())
+            .op(|| self.arg_op(index))?;
+        self.index += 1;
+
+        let inner = unsafe { buf.inner.assume_state::<InFlight>() };
+        Ok(DeviceBuffer::from_inner(inner, buf.len))
+    }
+
+    /// Bind a by-value scalar argument.
+    pub(crate) fn scalar<S: KernelScalar>(&mut self, val: &S) -> Result<()> {
+        let index = self.index;
+        self.kernel.set_arg(index, val).op(|| self.arg_op(index))?;
+        self.index += 1;
+        Ok(())
+    }
+
+    /// Bind a `__local` argument.
+    ///
+    /// Fails with `Error::KernelArgs` if it is empty, if its size overflows
+    /// or if all local arguments of this launch together exceed the device's
+    /// local memory.
+    pub(crate) fn local<T: KernelScalar>(&mut self, mem: &LocalMem<T>) -> Result<()> {
+        let index = self.index;
+        let Some(size) = mem.size_bytes() else {
+            return Err(self.arg_error(format!(
+                "local argument {index} of {} elements overflows the address space",
+                mem.len()
+            )));
+        };
+        if size == 0 {
+            return Err(self.arg_error(format!("local argument {index} has size 0")));
+        }
+
+        let Some(total) = u64::try_from(size)
+            .ok()
+            .and_then(|size| self.local_bytes.checked_add(size))
+        else {
+            return Err(self.arg_error(format!(
+                "local arguments up to {index} overflow the address space"
+            )));
+        };
+        let available = self.local_mem_limit()?;
+        if total > available {
+            return Err(self.arg_error(format!(
+                "local arguments need {total} bytes, device has {available} bytes local memory"
+            )));
+        }
+
+        self.kernel
+            .set_arg_local_buffer(index, size)
+            .op(|| self.arg_op(index))?;
+        self.local_bytes = total;
+        self.index += 1;
+        Ok(())
+    }
+
+    /// Final check after all arguments are bound: local arguments plus the
+    /// kernel's own `__local` variables must fit into the device's local
+    /// memory. Without local arguments nothing is queried.
+    pub(crate) fn finish(&mut self) -> Result<()> {
+        if self.local_bytes == 0 {
+            return Ok(());
+        }
+
+        // CL_KERNEL_LOCAL_MEM_SIZE enthält laut Spezifikation die gesetzten
+        // __local-Argumente; max() für Treiber, die sie nicht mitzählen
+        let used = self
+            .kernel
+            .get_local_mem_size(self.device)
+            .op(|| format!("query local memory of kernel `{}`", self.name))?
+            .max(self.local_bytes);
+        let available = self.local_mem_limit()?;
+        if used > available {
+            return Err(self.arg_error(format!(
+                "kernel needs {used} bytes local memory ({} in local arguments), device has {available} bytes",
+                self.local_bytes
+            )));
+        }
+        Ok(())
+    }
+
+    fn local_mem_limit(&mut self) -> Result<u64> {
+        if let Some(limit) = self.local_mem_limit {
+            return Ok(limit);
+        }
+        let limit = CLDevice::new(self.device).local_mem_size()?;
+        self.local_mem_limit = Some(limit);
+        Ok(limit)
+    }
+
+    fn arg_error(&self, msg: String) -> Error {
+        Error::KernelArgs {
+            kernel: self.name.to_owned(),
+            msg,
+        }
+    }
+
+    fn arg_op(&self, index: u32) -> String {
+        format!("set argument {index} of kernel `{}`", self.name)
+    }
+}
+
+//#####
+// ARGUMENT TRAITS
+//#####
+
+// Privat: KernelArg kann außerhalb des Crates nicht implementiert werden
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A single kernel argument.
+///
+/// Sealed; implemented for `DeviceBuffer<'brand, T, Written>`, every
+/// `KernelScalar` and `LocalMem<T>`.
+pub trait KernelArg<'brand>: sealed::Sealed {
+    /// What stays in flight while the kernel runs (`()` for scalars).
+    type InFlight: InFlightBuffers<'brand>;
+
+    /// Bind the argument at the next index and perform its state
+    /// transition (`Written` → `InFlight` for buffers).
+    #[doc(hidden)]
+    fn bind(self, binder: &mut ArgBinder<'_, 'brand>) -> Result<Self::InFlight>;
+}
+
+impl<T> sealed::Sealed for DeviceBuffer<'_, T, Written> {}
+
+impl<'brand, T> KernelArg<'brand> for DeviceBuffer<'brand, T, Written> {
+    type InFlight = DeviceBuffer<'brand, T, InFlight>;
+
+    fn bind(self, binder: &mut ArgBinder<'_, 'brand>) -> Result<Self::InFlight> {
+        binder.buffer(self)
+    }
+}
+
+impl<S: KernelScalar> sealed::Sealed for S {}
+
+impl<'brand, S: KernelScalar> KernelArg<'brand> for S {
+    type InFlight = ();
+
+    fn bind(self, binder: &mut ArgBinder<'_, 'brand>) -> Result<()> {
+        binder.scalar(&self)
+    }
+}
+
+impl<T: KernelScalar> sealed::Sealed for LocalMem<T> {}
+
+impl<'brand, T: KernelScalar> KernelArg<'brand> for LocalMem<T> {
+    type InFlight = ();
+
+    fn bind(self, binder: &mut ArgBinder<'_, 'brand>) -> Result<()> {
+        binder.local(&self)
+    }
+}
+
+/// A complete argument list, bound in order starting at index 0.
+pub trait KernelArgs<'brand>: ArgsSealed {
+    /// All buffers of the list in state `InFlight`.
+    type InFlight: InFlightBuffers<'brand>;
+
+    /// Bind every argument in order; buffers move to `InFlight`.
+    #[doc(hidden)]
+    fn bind(self, binder: &mut ArgBinder<'_, 'brand>) -> Result<Self::InFlight>;
+}
+
+macro_rules! impl_kernel_args_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> ArgsSealed for ($($name,)+) {}
+
+        impl<'brand, $($name: KernelArg<'brand>),+> KernelArgs<'brand> for ($($name,)+) {
+            type InFlight = ($($name::InFlight,)+);
+
+            #[allow(non_snake_case)]
+            fn bind(self, binder: &mut ArgBinder<'_, 'brand>) -> Result<Self::InFlight> {
+                let ($($name,)+) = self;
+                Ok(($($name.bind(binder)?,)+))
+            }
+        }
+    };
+}
+
+impl_kernel_args_tuple!(A);
+impl_kernel_args_tuple!(A, B);
+impl_kernel_args_tuple!(A, B, C);
+impl_kernel_args_tuple!(A, B, C, D);
+impl_kernel_args_tuple!(A, B, C, D, E);
+impl_kernel_args_tuple!(A, B, C, D, E, F);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G, H);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G, H, I);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_kernel_args_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q);
@@ -0,0 +1,140 @@
+//! Kernel metadata.
+//!
+//! - Argument metadata (`clGetKernelArgInfo`): only available if the
+//!   program was built with `-cl-kernel-arg-info`, which `ProgramBuilder`
+//!   always sets. Used to verify typed kernel signatures.
+//! - Work-group limits and memory usage per device
+//!   (`clGetKernelWorkGroupInfo`), e.g. to pick a local size.
+
+use opencl3::error_codes::CL_KERNEL_ARG_INFO_NOT_AVAILABLE;
+use opencl3::kernel::{
+    CL_KERNEL_ARG_ACCESS_READ_ONLY, CL_KERNEL_ARG_ACCESS_READ_WRITE,
+    CL_KERNEL_ARG_ACCESS_WRITE_ONLY, CL_KERNEL_ARG_ADDRESS_CONSTANT, CL_KERNEL_ARG_ADDRESS_GLOBAL,
+    CL_KERNEL_ARG_ADDRESS_LOCAL, CL_KERNEL_ARG_TYPE_CONST, CL_KERNEL_ARG_TYPE_PIPE,
+    CL_KERNEL_ARG_TYPE_RESTRICT, CL_KERNEL_ARG_TYPE_VOLATILE, Kernel as CLKernel,
+};
+use opencl3::types::cl_device_id;
+use serde::Serialize;
+
+use crate::error::Result;
+
+//#####
+// ARGUMENTS
+//#####
+
+/// Address space of a kernel parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AddressQualifier {
+    Global,
+    Constant,
+    Local,
+    /// By-value argument.
+    Private,
+}
+
+/// Image access qualifier; `None` for all non-image parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AccessQualifier {
+    ReadOnly,
+    WriteOnly,
+    ReadWrite,
+    None,
+}
+
+/// `CL_KERNEL_ARG_TYPE_QUALIFIER` bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct TypeQualifiers {
+    pub is_const: bool,
+    pub is_restrict: bool,
+    pub is_volatile: bool,
+    pub is_pipe: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KernelArgInfo {
+    pub name: String,
+    /// `CL_KERNEL_ARG_TYPE_NAME`, e.g. `"float*"` or `"uint"`.
+    pub type_name: String,
+    pub address: AddressQualifier,
+    pub access: AccessQualifier,
+    pub qualifiers: TypeQualifiers,
+}
+
+impl KernelArgInfo {
+    /// Query argument `index`; `None` if the driver has no argument info.
+    pub(crate) fn query(kernel: &CLKernel, index: u32) -> Result<Option<Self>> {
+        let address = match kernel.get_arg_address_qualifier(index) {
+            Ok(q) => q,
+            Err(e) if e.0 == CL_KERNEL_ARG_INFO_NOT_AVAILABLE => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let qualifiers = kernel.get_arg_type_qualifier(index)?;
+
+        Ok(Some(Self {
+            name: kernel.get_arg_name(index)?,
+            type_name: kernel.get_arg_type_name(index)?,
+            address: match address {
+                CL_KERNEL_ARG_ADDRESS_GLOBAL => AddressQualifier::Global,
+                CL_KERNEL_ARG_ADDRESS_CONSTANT => AddressQualifier::Constant,
+                CL_KERNEL_ARG_ADDRESS_LOCAL => AddressQualifier::Local,
+                _ => AddressQualifier::Private,
+            },
+            access: match kernel.get_arg_access_qualifier(index)? {
+                CL_KERNEL_ARG_ACCESS_READ_ONLY => AccessQualifier::ReadOnly,
+                CL_KERNEL_ARG_ACCESS_WRITE_ONLY => AccessQualifier::WriteOnly,
+                CL_KERNEL_ARG_ACCESS_READ_WRITE => AccessQualifier::ReadWrite,
+                _ => AccessQualifier::None,
+            },
+            qualifiers: TypeQualifiers {
+                is_const: qualifiers & CL_KERNEL_ARG_TYPE_CONST != 0,
+                is_restrict: qualifiers & CL_KERNEL_ARG_TYPE_RESTRICT != 0,
+                is_volatile: qualifiers & CL_KERNEL_ARG_TYPE_VOLATILE != 0,
+                is_pipe: qualifiers & CL_KERNEL_ARG_TYPE_PIPE != 0,
+            },
+        }))
+    }
+
+    /// Type name without whitespace; drivers differ in `"float *"` vs `"float*"`.
+    pub(crate) fn normalized_type(&self) -> String {
+        self.type_name.split_whitespace().collect()
+    }
+}
+
+//#####
+// WORK-GROUP INFO
+//#####
+
+/// Per-device limits and resource usage of a kernel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KernelWorkGroupInfo {
+    /// `CL_KERNEL_WORK_GROUP_SIZE`: largest local size this kernel can run with.
+    pub work_group_size: usize,
+    /// `CL_KERNEL_PREFERRED_WORK_GROUP_SIZE_MULTIPLE`, e.g. warp/wavefront size.
+    pub preferred_work_group_size_multiple: usize,
+    /// `reqd_work_group_size` attribute; `[0, 0, 0]` if not set.
+    pub compile_work_group_size: [usize; 3],
+    /// Local memory used by the kernel itself, in bytes.
+    pub local_mem_size: u64,
+    /// Private memory per work-item, in bytes.
+    pub private_mem_size: u64,
+}
+
+impl KernelWorkGroupInfo {
+    pub(crate) fn query(kernel: &CLKernel, device: cl_device_id) -> Result<Self> {
+        let mut compile_work_group_size = [0; 3];
+        for (dst, src) in compile_work_group_size
+            .iter_mut()
+            .zip(kernel.get_compile_work_group_size(device)?)
+        {
+            *dst = src;
+        }
+
+        Ok(Self {
+            work_group_size: kernel.get_work_group_size(device)?,
+            preferred_work_group_size_multiple: kernel.get_work_group_size_multiple(device)?,
+            compile_work_group_size,
+            local_mem_size: kernel.get_local_mem_size(device)?,
+            private_mem_size: kernel.get_private_mem_size(device)?,
+        })
+    }
+}
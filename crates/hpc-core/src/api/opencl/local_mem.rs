@@ -0,0 +1,56 @@
+//! `__local` kernel arguments.
+//!
+//! `LocalMem<T>` reserves work-group local memory for `len` elements of `T`
+//! (`clSetKernelArg` with a size and a null pointer). It holds no data on
+//! the host and takes no part in buffer state transitions. The local memory
+//! of a launch (all local arguments plus the kernel's own `__local`
+//! variables) is checked against `CL_DEVICE_LOCAL_MEM_SIZE`.
+
+use std::marker::PhantomData;
+
+use super::KernelScalar;
+
+/// `__local T*` argument with room for `len` elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalMem<T> {
+    len: usize,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T: KernelScalar> LocalMem<T> {
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            _type: PhantomData,
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Size in bytes passed to `clSetKernelArg`; `None` if it overflows `usize`.
+    pub fn size_bytes(&self) -> Option<usize> {
+        self.len.checked_mul(size_of::<T>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_bytes_is_checked() {
+        assert_eq!(LocalMem::<f32>::new(256).size_bytes(), Some(1024));
+        assert_eq!(
+            LocalMem::<u8>::new(usize::MAX).size_bytes(),
+            Some(usize::MAX)
+        );
+        assert_eq!(LocalMem::<u16>::new(usize::MAX / 2 + 1).size_bytes(), None);
+    }
+}
@@ -1,10 +1,41 @@
 //! High-level OpenCL API module.
 //! Exposes safe wrappers around the core OpenCL concepts used in this
 
+mod binary_cache;
+mod buffer_rect;
 mod context;
+mod context_builder;
+mod device;
+mod device_info;
 mod kernel;
+mod kernel_args;
+mod kernel_info;
+mod local_mem;
+mod nd_range;
+mod program;
+mod program_builder;
 mod queue;
+mod queue_builder;
+mod signature;
+pub mod vector;
 
+pub use buffer_rect::BufferRect;
+pub(crate) use buffer_rect::RectBytes;
 pub use context::Context;
-pub use kernel::Kernel;
-pub use queue::Queue;
+pub use context_builder::{ContextBuilder, DeviceType};
+pub use device::Device;
+pub use device_info::DeviceInfo;
+pub use kernel::{Kernel, KernelScalar};
+pub use kernel_args::{ArgBinder, KernelArg, KernelArgs};
+pub use kernel_info::{
+    AccessQualifier, AddressQualifier, KernelArgInfo, KernelWorkGroupInfo, TypeQualifiers,
+};
+pub use local_mem::LocalMem;
+pub use nd_range::NdRange;
+pub use program::{CompiledProgram, Program};
+pub use program_builder::{ClStd, ProgramBuilder};
+pub use queue::{NoProfiling, Profiling, ProfilingMode, Queue};
+pub use queue_builder::QueueBuilder;
+#[cfg(feature = "queue_hints")]
+pub use queue_builder::{QueuePriority, QueueThrottle};
+pub use signature::{ArgType, Buf, KernelSignature, Untyped};
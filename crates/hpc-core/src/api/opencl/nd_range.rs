@@ -0,0 +1,307 @@
+//! NDRange of a kernel launch (`clEnqueueNDRangeKernel`).
+//!
+//! `NdRange<D>` with `D` = 1, 2 or 3 holds the global size and optionally a
+//! local (work-group) size and a global offset, all with exactly `D`
+//! entries. A plain `usize` converts to a 1D range, `[usize; D]` to a `D`-dim
+//! range, so `kernel.launch(&queue, args, n)` stays a 1D launch.
+//!
+//! ```ignore
+//! let range = NdRange::d2(1024, 768).local([16, 16]);
+//! let (inflight, evt) = kernel.launch(&queue, args, range)?;
+//! ```
+
+use std::fmt;
+
+use opencl3::command_queue::CommandQueue;
+use opencl3::device::Device as CLDevice;
+use opencl3::event::Event;
+use opencl3::kernel::Kernel as CLKernel;
+
+use crate::error::{Error, Result, ResultExt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NdRange<const D: usize> {
+    global: [usize; D],
+    local: Option<[usize; D]>,
+    offset: Option<[usize; D]>,
+}
+
+impl NdRange<1> {
+    pub fn d1(x: usize) -> Self {
+        Self::from_global([x])
+    }
+}
+
+impl NdRange<2> {
+    pub fn d2(x: usize, y: usize) -> Self {
+        Self::from_global([x, y])
+    }
+}
+
+impl NdRange<3> {
+    pub fn d3(x: usize, y: usize, z: usize) -> Self {
+        Self::from_global([x, y, z])
+    }
+}
+
+impl<const D: usize> NdRange<D> {
+    // Privat: nur D = 1..=3 über d1/d2/d3 bzw. From
+    fn from_global(global: [usize; D]) -> Self {
+        Self {
+            global,
+            local: None,
+            offset: None,
+        }
+    }
+
+    /// Explicit work-group size; each global size must be a multiple of it.
+    pub fn local(mut self, local: [usize; D]) -> Self {
+        self.local = Some(local);
+        self
+    }
+
+    /// Global work-item offset (`get_global_id` starts here).
+    pub fn offset(mut self, offset: [usize; D]) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn dims(&self) -> u32 {
+        D as u32
+    }
+
+    pub fn global_size(&self) -> [usize; D] {
+        self.global
+    }
+
+    pub fn local_size(&self) -> Option<[usize; D]> {
+        self.local
+    }
+
+    pub fn global_offset(&self) -> Option<[usize; D]> {
+        self.offset
+    }
+
+    /// Total number of work-items.
+    pub fn work_items(&self) -> usize {
+        self.global.iter().product()
+    }
+
+    /// Check the range against the queue's device and the kernel.
+    ///
+    /// Device limits are only queried if a local size is set; without one
+    /// the driver picks a valid work-group size.
+    pub(crate) fn validate(&self, queue: &CommandQueue, kernel: &CLKernel) -> Result<()> {
+        if let Some(dim) = self.global.iter().position(|&g| g == 0) {
+            return Err(self.invalid(kernel, format!("global size is 0 in dimension {dim}")));
+        }
+
+        if let Some(offset) = &self.offset {
+            check_offset(&self.global, offset).map_err(|msg| self.invalid(kernel, msg))?;
+        }
+
+        let Some(local) = self.local else {
+            return Ok(());
+        };
+
+        let device_id = queue.device().op(|| "query queue device".to_owned())?;
+        let device = CLDevice::new(device_id);
+        let max_item_sizes = device.max_work_item_sizes()?;
+        // CL_KERNEL_WORK_GROUP_SIZE ist <= CL_DEVICE_MAX_WORK_GROUP_SIZE,
+        // kann aber wegen Register-/Local-Mem-Verbrauch kleiner sein
+        let max_group = device
+            .max_work_group_size()?
+            .min(kernel.get_work_group_size(device_id)?);
+
+        check_local(&self.global, &local, &max_item_sizes, max_group)
+            .map_err(|msg| self.invalid(kernel, msg))
+    }
+
+    /// Validate and enqueue `kernel` with this range.
+    pub(crate) fn enqueue(&self, queue: &CommandQueue, kernel: &CLKernel) -> Result<Event> {
+        self.validate(queue, kernel)?;
+
+        let ptr_or_null =
+            |v: &Option<[usize; D]>| v.as_ref().map_or(std::ptr::null(), |a| a.as_ptr());
+        let evt = queue
+            .enqueue_nd_range_kernel(
+                kernel.get(),
+                D as u32,
+                ptr_or_null(&self.offset),
+                self.global.as_ptr(),
+                ptr_or_null(&self.local),
+                &[],
+            )
+            .op(|| format!("launch kernel `{}` ({self})", kernel_name(kernel)))?;
+        Ok(evt)
+    }
+
+    fn invalid(&self, kernel: &CLKernel, msg: String) -> Error {
+        Error::InvalidNdRange {
+            kernel: kernel_name(kernel),
+            msg: format!("{msg} ({self})"),
+        }
+    }
+}
+
+/// `offset + global` must stay representable, the largest global id
+/// would overflow otherwise.
+fn check_offset(global: &[usize], offset: &[usize]) -> std::result::Result<(), String> {
+    for (dim, (&g, &o)) in global.iter().zip(offset).enumerate() {
+        if o.checked_add(g).is_none() {
+            return Err(format!(
+                "offset {o} plus global size {g} overflows in dimension {dim}"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Local size against the device limits, without any OpenCL call.
+fn check_local(
+    global: &[usize],
+    local: &[usize],
+    max_item_sizes: &[usize],
+    max_group: usize,
+) -> std::result::Result<(), String> {
+    if global.len() > max_item_sizes.len() {
+        return Err(format!(
+            "device supports {} dimensions",
+            max_item_sizes.len()
+        ));
+    }
+
+    for (dim, (&g, &l)) in global.iter().zip(local).enumerate() {
+        if l == 0 || l > max_item_sizes[dim] {
+            return Err(format!(
+                "local size {l} in dimension {dim} not in 1..={}",
+                max_item_sizes[dim]
+            ));
+        }
+        if g % l != 0 {
+            return Err(format!(
+                "global size {g} in dimension {dim} is no multiple of local size {l}"
+            ));
+        }
+    }
+
+    let group: usize = local.iter().product();
+    if group > max_group {
+        return Err(format!(
+            "work-group size {group} exceeds maximum {max_group}"
+        ));
+    }
+    Ok(())
+}
+
+// Name nur im Fehlerfall abfragen
+fn kernel_name(kernel: &CLKernel) -> String {
+    kernel.function_name().unwrap_or_default()
+}
+
+impl<const D: usize> fmt::Display for NdRange<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn sizes(f: &mut fmt::Formatter<'_>, v: &[usize]) -> fmt::Result {
+            let parts: Vec<String> = v.iter().map(usize::to_string).collect();
+            write!(f, "{}", parts.join("x"))
+        }
+
+        write!(f, "global size ")?;
+        sizes(f, &self.global)?;
+        if let Some(local) = &self.local {
+            write!(f, ", local size ")?;
+            sizes(f, local)?;
+        }
+        if let Some(offset) = &self.offset {
+            write!(f, ", offset ")?;
+            sizes(f, offset)?;
+        }
+        Ok(())
+    }
+}
+
+//#####
+// CONVERSIONS
+//#####
+
+impl From<usize> for NdRange<1> {
+    fn from(x: usize) -> Self {
+        Self::d1(x)
+    }
+}
+
+macro_rules! impl_from_array {
+    ($($d:literal),+) => {
+        $(impl From<[usize; $d]> for NdRange<$d> {
+            fn from(global: [usize; $d]) -> Self {
+                Self::from_global(global)
+            }
+        })+
+    };
+}
+
+impl_from_array!(1, 2, 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: [usize; 3] = [1024, 1024, 64];
+
+    #[test]
+    fn displays_all_parts() {
+        assert_eq!(NdRange::from(1000).to_string(), "global size 1000");
+        let range = NdRange::d2(1024, 768).local([16, 16]).offset([0, 8]);
+        assert_eq!(
+            range.to_string(),
+            "global size 1024x768, local size 16x16, offset 0x8"
+        );
+        assert_eq!(range.work_items(), 1024 * 768);
+        assert_eq!(range.dims(), 2);
+    }
+
+    #[test]
+    fn rejects_overflowing_offset() {
+        assert_eq!(check_offset(&[1024, 768], &[0, 8]), Ok(()));
+        assert_eq!(check_offset(&[1], &[usize::MAX - 1]), Ok(()));
+        assert_eq!(
+            check_offset(&[64, 2], &[0, usize::MAX - 1]).unwrap_err(),
+            format!(
+                "offset {} plus global size 2 overflows in dimension 1",
+                usize::MAX - 1
+            )
+        );
+    }
+
+    #[test]
+    fn accepts_local_within_limits() {
+        assert_eq!(check_local(&[1024, 768], &[16, 16], &ITEMS, 256), Ok(()));
+        assert_eq!(check_local(&[64, 64, 64], &[4, 4, 16], &ITEMS, 256), Ok(()));
+    }
+
+    #[test]
+    fn rejects_local_outside_limits() {
+        let err =
+            |g: &[usize], l: &[usize], items: &[usize]| check_local(g, l, items, 256).unwrap_err();
+        assert_eq!(
+            err(&[64], &[0], &ITEMS),
+            "local size 0 in dimension 0 not in 1..=1024"
+        );
+        assert_eq!(
+            err(&[128, 128, 128], &[1, 1, 128], &ITEMS),
+            "local size 128 in dimension 2 not in 1..=64"
+        );
+        assert_eq!(
+            err(&[100, 64], &[16, 16], &ITEMS),
+            "global size 100 in dimension 0 is no multiple of local size 16"
+        );
+        assert_eq!(
+            err(&[64, 64], &[32, 32], &ITEMS),
+            "work-group size 1024 exceeds maximum 256"
+        );
+        assert_eq!(
+            err(&[64, 64, 64], &[1, 1, 1], &ITEMS[..2]),
+            "device supports 2 dimensions"
+        );
+    }
+}
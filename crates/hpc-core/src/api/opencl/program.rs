@@ -0,0 +1,310 @@
+//! Branded OpenCL programs.
+//!
+//! `Program<'brand>` is an executable program built for all devices of its
+//! context; it is built once and hands out any number of kernels by name. `CompiledProgram<'brand>` is a
+//! compiled but unlinked object (`clCompileProgram`) that can only be
+//! linked, not launched. Both are created through `ProgramBuilder`.
+//!
+//! opencl3's `Program` cannot represent the result of `clLinkProgram`, so
+//! the raw `cl_program` is owned here directly.
+
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use opencl3::device::Device as CLDevice;
+use opencl3::error_codes::ClError;
+use opencl3::kernel::{Kernel as CLKernel, create_kernel, create_kernels_in_program};
+use opencl3::program::{
+    CL_PROGRAM_BINARIES, CL_PROGRAM_BUILD_LOG, CL_PROGRAM_DEVICES, CL_PROGRAM_KERNEL_NAMES,
+    get_program_build_info, get_program_info, link_program, release_program,
+};
+use opencl3::types::{cl_device_id, cl_program};
+
+use super::{Context, Kernel};
+use crate::error::{BuildLog, Error, Result, ResultExt};
+
+//#####
+// RAW HANDLE
+//#####
+
+/// Owned `cl_program`, released on drop. Kernels keep it alive via `Arc`.
+#[derive(Debug)]
+pub(crate) struct RawProgram(cl_program);
+
+// OpenCL-Objekte sind thread-safe (wie in opencl3)
+unsafe impl Send for RawProgram {}
+unsafe impl Sync for RawProgram {}
+
+impl RawProgram {
+    pub(crate) fn new(program: cl_program) -> Self {
+        Self(program)
+    }
+
+    pub(crate) fn get(&self) -> cl_program {
+        self.0
+    }
+
+    /// Compiler output of every device with a non-empty log.
+    pub(crate) fn build_logs(&self, devices: &[cl_device_id]) -> Vec<BuildLog> {
+        devices
+            .iter()
+            .filter_map(|&device| {
+                let log: String = get_program_build_info(self.0, device, CL_PROGRAM_BUILD_LOG)
+                    .ok()?
+                    .into();
+                let log = log.trim_end_matches('\0').trim();
+                (!log.is_empty()).then(|| BuildLog {
+                    device: CLDevice::new(device).name().unwrap_or_default(),
+                    log: log.to_owned(),
+                })
+            })
+            .collect()
+    }
+
+    /// `CL_PROGRAM_BINARIES` with the device each binary belongs to.
+    pub(crate) fn binaries(&self) -> Result<Vec<(cl_device_id, Vec<u8>)>> {
+        let devices: Vec<isize> = get_program_info(self.0, CL_PROGRAM_DEVICES)
+            .map_err(ClError)?
+            .into();
+        let binaries: Vec<Vec<u8>> = get_program_info(self.0, CL_PROGRAM_BINARIES)
+            .map_err(ClError)?
+            .into();
+        Ok(devices
+            .into_iter()
+            .map(|d| d as cl_device_id)
+            .zip(binaries)
+            .collect())
+    }
+
+    /// Turn a failed build/compile/link into `Error::Build` with the logs.
+    pub(crate) fn build_error(
+        &self,
+        op: &'static str,
+        code: i32,
+        devices: &[cl_device_id],
+    ) -> Error {
+        Error::Build {
+            op,
+            code: ClError(code),
+            logs: self.build_logs(devices),
+        }
+    }
+}
+
+impl Drop for RawProgram {
+    fn drop(&mut self) {
+        let _ = release_program(self.0);
+    }
+}
+
+//#####
+// PROGRAM
+//#####
+
+/// Executable program of context `'brand`.
+#[derive(Debug)]
+pub struct Program<'brand> {
+    raw: Arc<RawProgram>,
+    options: String,
+    _brand: PhantomData<fn(&'brand ()) -> &'brand ()>,
+}
+
+impl<'brand> Program<'brand> {
+    /// Build `src` with the default options (see `ProgramBuilder`).
+    pub fn from_source(ctx: &'brand Context<'brand>, src: &str) -> Result<Self> {
+        ctx.program_builder().source(src).build()
+    }
+
+    /// Build a SPIR-V module with the default options, see `ProgramBuilder::il`.
+    #[cfg(feature = "spirv")]
+    pub fn from_il(ctx: &'brand Context<'brand>, il: &[u8]) -> Result<Self> {
+        ctx.program_builder().il(il).build()
+    }
+
+    pub(crate) fn new(raw: RawProgram, options: String) -> Self {
+        Self {
+            raw: Arc::new(raw),
+            options,
+            _brand: PhantomData,
+        }
+    }
+
+    /// Link compiled objects into one executable program (`clLinkProgram`).
+    ///
+    /// The result records the compile options of `objects` (see `options`);
+    /// those that are also linker options, such as `-cl-fast-relaxed-math`,
+    /// are passed to `clLinkProgram`. On failure, `Error::Build` contains
+    /// the linker log of every device.
+    pub fn link(
+        ctx: &'brand Context<'brand>,
+        objects: &[&CompiledProgram<'brand>],
+    ) -> Result<Self> {
+        if objects.is_empty() {
+            return Err(Error::Msg(
+                "link needs at least one compiled program".into(),
+            ));
+        }
+
+        // Verschiedene Optionen der Objekte in Reihenfolge, ohne Dubletten
+        let mut compile_options: Vec<&str> = Vec::new();
+        for object in objects {
+            if !compile_options.contains(&object.options()) {
+                compile_options.push(object.options());
+            }
+        }
+        let options = compile_options.join(" ");
+        let link_options = CString::new(linker_options(&options))
+            .map_err(|_| Error::Msg(format!("invalid link options `{options}`")))?;
+
+        let devices = device_ids(ctx);
+        let inputs: Vec<cl_program> = objects.iter().map(|o| o.raw.get()).collect();
+        let raw = link_program(
+            ctx.raw().get(),
+            &devices,
+            &link_options,
+            &inputs,
+            None,
+            std::ptr::null_mut(),
+        )
+        .map_err(|code| {
+            // cl3 verwirft das Programm des fehlgeschlagenen Links,
+            // daher die Logs der Eingabeprogramme
+            let logs = objects
+                .iter()
+                .flat_map(|o| o.raw.build_logs(&devices))
+                .collect();
+            Error::Build {
+                op: "link",
+                code: ClError(code),
+                logs,
+            }
+        })?;
+
+        Ok(Self::new(RawProgram::new(raw), options))
+    }
+
+    /// Create the kernel `name`.
+    pub fn kernel(&self, name: &str) -> Result<Kernel<'brand>> {
+        let c_name =
+            CString::new(name).map_err(|_| Error::Msg(format!("invalid kernel name `{name}`")))?;
+        let raw = create_kernel(self.raw.get(), &c_name)
+            .map_err(ClError)
+            .op(|| format!("create kernel `{name}`"))?;
+        Kernel::new(CLKernel::new(raw), name, Arc::clone(&self.raw))
+    }
+
+    /// All kernels of the program, in the order of `kernel_names`.
+    pub fn kernels(&self) -> Result<Vec<Kernel<'brand>>> {
+        let raw = create_kernels_in_program(self.raw.get())
+            .map_err(ClError)
+            .op(|| "create kernels in program".to_owned())?;
+        // Alle Handles zuerst wrappen, damit sie bei Fehlern freigegeben werden
+        let kernels: Vec<CLKernel> = raw.into_iter().map(CLKernel::new).collect();
+        kernels
+            .into_iter()
+            .map(|inner| {
+                let name = inner.function_name()?;
+                Kernel::new(inner, &name, Arc::clone(&self.raw))
+            })
+            .collect()
+    }
+
+    /// Names of all kernels in the program (`CL_PROGRAM_KERNEL_NAMES`).
+    pub fn kernel_names(&self) -> Result<Vec<String>> {
+        let names: String = get_program_info(self.raw.get(), CL_PROGRAM_KERNEL_NAMES)
+            .map_err(ClError)
+            .op(|| "query program kernel names".to_owned())?
+            .into();
+        Ok(names
+            .trim_end_matches('\0')
+            .split(';')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(str::to_owned)
+            .collect())
+    }
+
+    pub(crate) fn binaries(&self) -> Result<Vec<(cl_device_id, Vec<u8>)>> {
+        self.raw.binaries()
+    }
+
+    /// Options the program was built with; empty for linked programs.
+    pub fn options(&self) -> &str {
+        &self.options
+    }
+
+    pub fn raw(&self) -> cl_program {
+        self.raw.get()
+    }
+}
+
+/// Compiled, unlinked program object of context `'brand`.
+///
+/// Created by `ProgramBuilder::compile`; only `Program::link` accepts it.
+#[derive(Debug)]
+pub struct CompiledProgram<'brand> {
+    raw: RawProgram,
+    options: String,
+    _brand: PhantomData<fn(&'brand ()) -> &'brand ()>,
+}
+
+impl CompiledProgram<'_> {
+    pub(crate) fn new(raw: RawProgram, options: String) -> Self {
+        Self {
+            raw,
+            options,
+            _brand: PhantomData,
+        }
+    }
+
+    /// Options the object was compiled with.
+    pub fn options(&self) -> &str {
+        &self.options
+    }
+
+    pub fn raw(&self) -> cl_program {
+        self.raw.get()
+    }
+}
+
+pub(crate) fn device_ids(ctx: &Context<'_>) -> Vec<cl_device_id> {
+    ctx.devices().iter().map(|d| d.id()).collect()
+}
+
+/// The options in `options` that `clLinkProgram` accepts as well; compile
+/// options such as `-D` or `-cl-std` would fail the link.
+fn linker_options(options: &str) -> String {
+    const LINK: &[&str] = &[
+        "-cl-denorms-are-zero",
+        "-cl-no-signed-zeros",
+        "-cl-unsafe-math-optimizations",
+        "-cl-finite-math-only",
+        "-cl-fast-relaxed-math",
+        "-cl-no-subgroup-ifp",
+    ];
+    let mut kept: Vec<&str> = Vec::new();
+    for option in options.split_whitespace().filter(|o| LINK.contains(o)) {
+        if !kept.contains(&option) {
+            kept.push(option);
+        }
+    }
+    kept.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_linker_options() {
+        assert_eq!(
+            linker_options(
+                "-cl-kernel-arg-info -D TILE=16 -cl-std=CL2.0 -cl-fast-relaxed-math \
+                 -I \"my dir\" -cl-denorms-are-zero -cl-fast-relaxed-math"
+            ),
+            "-cl-fast-relaxed-math -cl-denorms-are-zero"
+        );
+        assert_eq!(linker_options("-cl-kernel-arg-info -D N=4"), "");
+    }
+}
@@ -0,0 +1,314 @@
+//! Program creation with build options.
+//!
+//! `ProgramBuilder` collects sources, embedded headers and build options
+//! (`-D`, `-I`, `-cl-std`, optimisation flags) and then either builds an
+//! executable `Program` or compiles a `CompiledProgram` for `Program::link`.
+//! `-cl-kernel-arg-info` is always set, `Kernel::typed` needs it.
+//!
+//! With the `spirv` feature, `il` takes a SPIR-V module instead of source
+//! (`clCreateProgramWithIL`); every device of the context must report
+//! SPIR-V in `CL_DEVICE_IL_VERSION`.
+//!
+//! ```ignore
+//! let program = ctx
+//!     .program_builder()
+//!     .source(src)
+//!     .define("TILE", 16)
+//!     .std(ClStd::Cl2_0)
+//!     .option("-cl-fast-relaxed-math")
+//!     .build()?;
+//! ```
+
+use std::ffi::{CStr, CString};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use opencl3::program::{
+    build_program, compile_program, create_program_with_binary, create_program_with_source,
+};
+use opencl3::types::{cl_device_id, cl_program};
+
+use super::binary_cache::{BinaryCache, include_parts};
+use super::program::{RawProgram, device_ids};
+use super::{CompiledProgram, Context, Program};
+use crate::error::{Error, Result, ResultExt};
+
+const KERNEL_ARG_INFO: &str = "-cl-kernel-arg-info";
+
+/// OpenCL C version passed as `-cl-std`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClStd {
+    Cl1_1,
+    Cl1_2,
+    Cl2_0,
+    Cl3_0,
+}
+
+impl ClStd {
+    fn option(self) -> &'static str {
+        match self {
+            ClStd::Cl1_1 => "-cl-std=CL1.1",
+            ClStd::Cl1_2 => "-cl-std=CL1.2",
+            ClStd::Cl2_0 => "-cl-std=CL2.0",
+            ClStd::Cl3_0 => "-cl-std=CL3.0",
+        }
+    }
+}
+
+/// Builder for a `Program`, obtained from `Context::program_builder`.
+#[must_use]
+#[derive(Debug)]
+pub struct ProgramBuilder<'brand> {
+    ctx: &'brand Context<'brand>,
+    sources: Vec<String>,
+    /// SPIR-V module instead of `sources`.
+    #[cfg(feature = "spirv")]
+    il: Option<Vec<u8>>,
+    /// Embedded headers: (include name, source).
+    headers: Vec<(String, String)>,
+    /// `-I` directories, their files go into the cache key.
+    include_dirs: Vec<PathBuf>,
+    options: Vec<String>,
+}
+
+impl<'brand> ProgramBuilder<'brand> {
+    pub(crate) fn new(ctx: &'brand Context<'brand>) -> Self {
+        Self {
+            ctx,
+            sources: Vec::new(),
+            #[cfg(feature = "spirv")]
+            il: None,
+            headers: Vec::new(),
+            include_dirs: Vec::new(),
+            options: vec![KERNEL_ARG_INFO.to_owned()],
+        }
+    }
+
+    /// Add a source string; several sources form one program.
+    pub fn source(mut self, src: impl Into<String>) -> Self {
+        self.sources.push(src.into());
+        self
+    }
+
+    /// Create the program from a SPIR-V module (e.g. from `llvm-spirv`).
+    ///
+    /// Cannot be combined with `source`. `build` fails with
+    /// `Error::Unsupported` if a device of the context does not accept SPIR-V.
+    #[cfg(feature = "spirv")]
+    pub fn il(mut self, il: impl Into<Vec<u8>>) -> Self {
+        self.il = Some(il.into());
+        self
+    }
+
+    /// Header available to `#include "name"` without touching the file
+    /// system. Setting headers makes `build` compile and link separately.
+    pub fn header(mut self, name: impl Into<String>, src: impl Into<String>) -> Self {
+        self.headers.push((name.into(), src.into()));
+        self
+    }
+
+    /// Preprocessor define, `-D name=value`.
+    pub fn define(mut self, name: &str, value: impl Display) -> Self {
+        self.options.push(format!("-D {name}={value}"));
+        self
+    }
+
+    /// Include directory, `-I path`.
+    ///
+    /// With a binary cache, every file below `path` is hashed into the
+    /// cache key on each `build`; keep the directory small.
+    pub fn include_dir(mut self, path: impl AsRef<Path>) -> Self {
+        self.include_dirs.push(path.as_ref().to_owned());
+        let path = path.as_ref().display().to_string();
+        self.options.push(if path.contains(char::is_whitespace) {
+            format!("-I \"{path}\"")
+        } else {
+            format!("-I {path}")
+        });
+        self
+    }
+
+    /// OpenCL C version (`-cl-std`).
+    pub fn std(mut self, std: ClStd) -> Self {
+        self.options.push(std.option().to_owned());
+        self
+    }
+
+    /// Any other compiler option, e.g. `-cl-fast-relaxed-math` or `-cl-opt-disable`.
+    pub fn option(mut self, option: impl Into<String>) -> Self {
+        self.options.push(option.into());
+        self
+    }
+
+    /// The option string passed to the compiler.
+    pub fn options(&self) -> String {
+        self.options.join(" ")
+    }
+
+    /// Build an executable program for all devices of the context.
+    ///
+    /// With a binary cache on the context, cached binaries are used if
+    /// present and valid, otherwise the fresh binaries are stored. Files
+    /// below `include_dir` paths are part of the cache key. On
+    /// failure, `Error::Build` contains the compiler log of every device.
+    pub fn build(self) -> Result<Program<'brand>> {
+        let devices = device_ids(self.ctx);
+        let options = self.options();
+
+        let cache = self.ctx.binary_cache().and_then(|dir| {
+            let includes = include_parts(&self.include_dirs)?;
+            let mut parts: Vec<&[u8]> = self.sources.iter().map(String::as_bytes).collect();
+            #[cfg(feature = "spirv")]
+            parts.extend(self.il.as_deref());
+            for (name, src) in &self.headers {
+                parts.extend([name.as_bytes(), src.as_bytes()]);
+            }
+            parts.extend(includes.iter().map(Vec::as_slice));
+            parts.push(options.as_bytes());
+            BinaryCache::new(dir, &devices, &parts)
+        });
+        if let Some(program) = cache.as_ref().and_then(|c| self.load_cached(c, &devices)) {
+            return Ok(program);
+        }
+
+        let program = self.build_from_source(&devices)?;
+        if let Some(cache) = &cache
+            && let Ok(binaries) = program.binaries()
+        {
+            cache.store(&binaries);
+        }
+        Ok(program)
+    }
+
+    fn build_from_source(self, devices: &[cl_device_id]) -> Result<Program<'brand>> {
+        if !self.headers.is_empty() {
+            let ctx = self.ctx;
+            let object = self.compile()?;
+            return Program::link(ctx, &[&object]);
+        }
+
+        let (raw, options) = self.create()?;
+        build_program(
+            raw.get(),
+            devices,
+            &c_string(&options)?,
+            None,
+            std::ptr::null_mut(),
+        )
+        .map_err(|code| raw.build_error("build", code, devices))?;
+        Ok(Program::new(raw, options))
+    }
+
+    /// Program from cached binaries; `None` falls back to the source build.
+    fn load_cached(
+        &self,
+        cache: &BinaryCache,
+        devices: &[cl_device_id],
+    ) -> Option<Program<'brand>> {
+        let binaries = cache.load()?;
+        let binaries: Vec<&[u8]> = binaries.iter().map(Vec::as_slice).collect();
+        let raw = create_program_with_binary(self.ctx.raw().get(), devices, &binaries).ok()?;
+        let raw = RawProgram::new(raw);
+
+        let options = self.options();
+        build_program(
+            raw.get(),
+            devices,
+            &c_string(&options).ok()?,
+            None,
+            std::ptr::null_mut(),
+        )
+        .ok()?;
+        Some(Program::new(raw, options))
+    }
+
+    /// Compile without linking (`clCompileProgram`).
+    pub fn compile(self) -> Result<CompiledProgram<'brand>> {
+        let (raw, options) = self.create()?;
+        let devices = device_ids(self.ctx);
+
+        let headers = self
+            .headers
+            .iter()
+            .map(|(_, src)| self.create_program(&[src.as_str()]))
+            .collect::<Result<Vec<_>>>()?;
+        let header_programs: Vec<cl_program> = headers.iter().map(RawProgram::get).collect();
+        let header_names = self
+            .headers
+            .iter()
+            .map(|(name, _)| c_string(name))
+            .collect::<Result<Vec<_>>>()?;
+        let header_names: Vec<&CStr> = header_names.iter().map(CString::as_c_str).collect();
+
+        compile_program(
+            raw.get(),
+            &devices,
+            &c_string(&options)?,
+            &header_programs,
+            &header_names,
+            None,
+            std::ptr::null_mut(),
+        )
+        .map_err(|code| raw.build_error("compile", code, &devices))?;
+        Ok(CompiledProgram::new(raw, options))
+    }
+
+    fn create(&self) -> Result<(RawProgram, String)> {
+        #[cfg(feature = "spirv")]
+        if let Some(il) = &self.il {
+            if !self.sources.is_empty() {
+                return Err(Error::Msg("program has both source and IL".into()));
+            }
+            return Ok((self.create_program_il(il)?, self.options()));
+        }
+
+        if self.sources.is_empty() {
+            return Err(Error::Msg("program has no source".into()));
+        }
+        let sources: Vec<&str> = self.sources.iter().map(String::as_str).collect();
+        Ok((self.create_program(&sources)?, self.options()))
+    }
+
+    fn create_program(&self, sources: &[&str]) -> Result<RawProgram> {
+        let raw = create_program_with_source(self.ctx.raw().get(), sources)
+            .map_err(opencl3::error_codes::ClError)
+            .op(|| "create program from source".to_owned())?;
+        Ok(RawProgram::new(raw))
+    }
+}
+
+#[cfg(feature = "spirv")]
+impl ProgramBuilder<'_> {
+    fn create_program_il(&self, il: &[u8]) -> Result<RawProgram> {
+        // SPIR-V magic number 0x07230203, in either byte order
+        let magic = il.first_chunk::<4>().copied().unwrap_or_default();
+        if u32::from_le_bytes(magic) != SPIRV_MAGIC && u32::from_be_bytes(magic) != SPIRV_MAGIC {
+            return Err(Error::Msg("IL is not a SPIR-V module".into()));
+        }
+
+        for device in self.ctx.devices() {
+            let dev = opencl3::device::Device::new(device.id());
+            // CL_DEVICE_IL_VERSION fehlt vor OpenCL 2.1 ganz -> Fehler = keine Unterstützung
+            let il_version = dev.il_version().unwrap_or_default();
+            if !il_version.contains("SPIR-V") {
+                return Err(Error::Unsupported(format!(
+                    "device `{}` does not accept SPIR-V (CL_DEVICE_IL_VERSION `{}`)",
+                    dev.name().unwrap_or_default(),
+                    il_version.trim_end_matches('\0')
+                )));
+            }
+        }
+
+        let raw = opencl3::program::create_program_with_il(self.ctx.raw().get(), il)
+            .map_err(opencl3::error_codes::ClError)
+            .op(|| format!("create program from {} bytes of IL", il.len()))?;
+        Ok(RawProgram::new(raw))
+    }
+}
+
+#[cfg(feature = "spirv")]
+const SPIRV_MAGIC: u32 = 0x0723_0203;
+
+fn c_string(s: &str) -> Result<CString> {
+    CString::new(s).map_err(|_| Error::Msg(format!("`{s}` contains a NUL byte")))
+}
@@ -1,23 +1,123 @@
 // src/api/queue.rs
 
-use opencl3::command_queue::CommandQueue as CLQueue;
+use opencl3::command_queue::{CL_QUEUE_PROFILING_ENABLE, CommandQueue as CLQueue};
+use opencl3::types::cl_device_id;
 use std::marker::PhantomData;
+
+use super::{Context, Device};
+use crate::error::{Error, Result};
+
+//#####
+// PROFILING MODE
+//#####
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Type-level flag whether a queue was created with `CL_QUEUE_PROFILING_ENABLE`.
+///
+/// Sealed: only `Profiling` and `NoProfiling` exist.
+pub trait ProfilingMode: sealed::Sealed + std::fmt::Debug + 'static {
+    const ENABLED: bool;
+}
+
+/// Queue created with `CL_QUEUE_PROFILING_ENABLE`.
+#[derive(Debug)]
+pub struct Profiling;
+
+/// Default queue, no profiling information is recorded.
+#[derive(Debug)]
+pub struct NoProfiling;
+
+impl sealed::Sealed for Profiling {}
+impl sealed::Sealed for NoProfiling {}
+
+impl ProfilingMode for Profiling {
+    const ENABLED: bool = true;
+}
+
+impl ProfilingMode for NoProfiling {
+    const ENABLED: bool = false;
+}
+
 //#####
 // QUEUE
 //#####
 
 #[must_use]
 #[derive(Debug)]
-pub struct Queue<'brand> {
+pub struct Queue<'brand, P: ProfilingMode = NoProfiling> {
     pub(crate) inner: CLQueue,
+    pub(crate) device: cl_device_id,
     pub(crate) _brand: PhantomData<fn(&'brand ()) -> &'brand ()>, // Branding Lifetime
+    pub(crate) _profiling: PhantomData<P>,
 }
 
 impl<'brand> Queue<'brand> {
+    /// Wrap an existing opencl3 command queue.
+    ///
+    /// Fails with `Error::HandleMismatch` unless the queue was created on
+    /// `ctx` and on one of its devices.
+    pub fn from_raw(ctx: &'brand Context<'brand>, queue: CLQueue) -> Result<Self> {
+        Self::wrap(ctx, queue)
+    }
+}
+
+impl<'brand> Queue<'brand, Profiling> {
+    /// Like `Queue::from_raw`, but additionally requires that `queue` has
+    /// `CL_QUEUE_PROFILING_ENABLE` set.
+    pub fn from_raw_profiling(ctx: &'brand Context<'brand>, queue: CLQueue) -> Result<Self> {
+        if queue.properties()? & CL_QUEUE_PROFILING_ENABLE == 0 {
+            return Err(Error::HandleMismatch(
+                "queue was not created with CL_QUEUE_PROFILING_ENABLE".into(),
+            ));
+        }
+        Self::wrap(ctx, queue)
+    }
+}
+
+impl<'brand, P: ProfilingMode> Queue<'brand, P> {
+    pub(crate) fn new(inner: CLQueue, device: cl_device_id) -> Self {
+        Self {
+            inner,
+            device,
+            _brand: PhantomData,
+            _profiling: PhantomData,
+        }
+    }
+
+    fn wrap(ctx: &'brand Context<'brand>, queue: CLQueue) -> Result<Self> {
+        if queue.context()? != ctx.raw().get() {
+            return Err(Error::HandleMismatch(
+                "queue belongs to a different context".into(),
+            ));
+        }
+
+        let device = queue.device()?;
+        if !ctx.devices().iter().any(|d| d.id() == device) {
+            return Err(Error::HandleMismatch(
+                "queue device is not part of the context".into(),
+            ));
+        }
+
+        Ok(Self::new(queue, device))
+    }
+
     /// Low-Level-Zugriff auf die OpenCL CommandQueue
     ///
     /// Normalerweise nicht nötig, da die High-Level API alles abdeckt.
     pub fn raw(&self) -> &CLQueue {
         &self.inner
     }
+
+    /// The device this queue submits to.
+    pub fn device(&self) -> Device<'brand> {
+        Device::new(self.device)
+    }
+
+    /// Whether profiling is enabled; fixed by the queue type.
+    pub fn is_profiling(&self) -> bool {
+        P::ENABLED
+    }
 }
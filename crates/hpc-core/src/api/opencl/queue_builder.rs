@@ -0,0 +1,217 @@
+//! Command-queue creation with properties.
+//!
+//! `QueueBuilder` collects the `cl_command_queue_properties` bits and the
+//! target device before creating the queue. Enabling profiling changes the
+//! queue type to `Queue<'brand, Profiling>`, so profiling queries can only
+//! be made on events of queues that actually record timestamps.
+//!
+//! Priority and throttle hints (`cl_khr_priority_hints`,
+//! `cl_khr_throttle_hints`) need `clCreateCommandQueueWithPropertiesKHR`
+//! and are only available with the `queue_hints` feature.
+//!
+//! There is deliberately no queue size setter: `CL_QUEUE_SIZE` is only
+//! valid together with `CL_QUEUE_ON_DEVICE`, and an on-device queue only
+//! accepts device-side enqueues from kernels. None of the host operations
+//! of `Queue` (transfers, launches, maps) would work on it.
+
+use opencl3::command_queue::{
+    CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE, CL_QUEUE_PROFILING_ENABLE, CommandQueue as CLQueue,
+};
+use opencl3::types::cl_command_queue_properties;
+use std::marker::PhantomData;
+
+use super::queue::{NoProfiling, Profiling, ProfilingMode};
+use super::{Context, Device, Queue};
+use crate::error::{Error, Result, ResultExt};
+
+//#####
+// HINTS
+//#####
+
+/// Scheduling priority hint (`cl_khr_priority_hints`).
+#[cfg(feature = "queue_hints")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueuePriority {
+    High,
+    Medium,
+    Low,
+}
+
+/// Power/throttle hint (`cl_khr_throttle_hints`).
+#[cfg(feature = "queue_hints")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueThrottle {
+    High,
+    Medium,
+    Low,
+}
+
+#[cfg(feature = "queue_hints")]
+impl QueuePriority {
+    fn raw(self) -> cl3::ext::cl_queue_properties_khr {
+        use cl3::ext::*;
+        (match self {
+            QueuePriority::High => CL_QUEUE_PRIORITY_HIGH_KHR,
+            QueuePriority::Medium => CL_QUEUE_PRIORITY_MED_KHR,
+            QueuePriority::Low => CL_QUEUE_PRIORITY_LOW_KHR,
+        }) as cl3::ext::cl_queue_properties_khr
+    }
+}
+
+#[cfg(feature = "queue_hints")]
+impl QueueThrottle {
+    fn raw(self) -> cl3::ext::cl_queue_properties_khr {
+        use cl3::ext::*;
+        (match self {
+            QueueThrottle::High => CL_QUEUE_THROTTLE_HIGH_KHR,
+            QueueThrottle::Medium => CL_QUEUE_THROTTLE_MED_KHR,
+            QueueThrottle::Low => CL_QUEUE_THROTTLE_LOW_KHR,
+        }) as cl3::ext::cl_queue_properties_khr
+    }
+}
+
+//#####
+// BUILDER
+//#####
+
+/// Builder for a `Queue`, obtained from `Context::queue_builder`.
+///
+/// Out-of-order execution is safe with the typestate API: every operation
+/// either blocks until it is complete or returns an `EventToken` that must
+/// be waited on before the buffer can be used again.
+#[must_use]
+#[derive(Debug)]
+pub struct QueueBuilder<'brand, P: ProfilingMode = NoProfiling> {
+    ctx: &'brand Context<'brand>,
+    device: Device<'brand>,
+    out_of_order: bool,
+    #[cfg(feature = "queue_hints")]
+    priority: Option<QueuePriority>,
+    #[cfg(feature = "queue_hints")]
+    throttle: Option<QueueThrottle>,
+    _profiling: PhantomData<P>,
+}
+
+impl<'brand> QueueBuilder<'brand> {
+    pub(crate) fn new(ctx: &'brand Context<'brand>) -> Self {
+        Self {
+            ctx,
+            device: ctx.primary_device(),
+            out_of_order: false,
+            #[cfg(feature = "queue_hints")]
+            priority: None,
+            #[cfg(feature = "queue_hints")]
+            throttle: None,
+            _profiling: PhantomData,
+        }
+    }
+
+    /// Set `CL_QUEUE_PROFILING_ENABLE`; the queue becomes a `Queue<'brand, Profiling>`.
+    pub fn profiling(self) -> QueueBuilder<'brand, Profiling> {
+        QueueBuilder {
+            ctx: self.ctx,
+            device: self.device,
+            out_of_order: self.out_of_order,
+            #[cfg(feature = "queue_hints")]
+            priority: self.priority,
+            #[cfg(feature = "queue_hints")]
+            throttle: self.throttle,
+            _profiling: PhantomData,
+        }
+    }
+}
+
+impl<'brand, P: ProfilingMode> QueueBuilder<'brand, P> {
+    /// Create the queue on `device` instead of the primary device.
+    pub fn device(mut self, device: Device<'brand>) -> Self {
+        self.device = device;
+        self
+    }
+
+    /// Set `CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE`.
+    pub fn out_of_order(mut self) -> Self {
+        self.out_of_order = true;
+        self
+    }
+
+    /// Request a scheduling priority; needs `cl_khr_priority_hints`.
+    #[cfg(feature = "queue_hints")]
+    pub fn priority(mut self, priority: QueuePriority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Request a throttle level; needs `cl_khr_throttle_hints`.
+    #[cfg(feature = "queue_hints")]
+    pub fn throttle(mut self, throttle: QueueThrottle) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    fn properties(&self) -> cl_command_queue_properties {
+        let mut props = 0;
+        if P::ENABLED {
+            props |= CL_QUEUE_PROFILING_ENABLE;
+        }
+        if self.out_of_order {
+            props |= CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE;
+        }
+        props
+    }
+
+    /// Create the queue.
+    ///
+    /// Fails with `Error::DeviceNotFound` if the device is not part of the
+    /// context and with `Error::Msg` if a requested hint extension is missing.
+    pub fn build(self) -> Result<Queue<'brand, P>> {
+        if !self.ctx.devices().contains(&self.device) {
+            return Err(Error::DeviceNotFound(
+                "device is not part of this context".into(),
+            ));
+        }
+
+        #[cfg(feature = "queue_hints")]
+        if self.priority.is_some() || self.throttle.is_some() {
+            return self.build_with_hints();
+        }
+
+        let q = CLQueue::create(self.ctx.raw(), self.device.id(), self.properties())
+            .op(|| format!("create queue (properties {:#x})", self.properties()))?;
+        Ok(Queue::new(q, self.device.id()))
+    }
+
+    #[cfg(feature = "queue_hints")]
+    fn build_with_hints(self) -> Result<Queue<'brand, P>> {
+        use cl3::ext::{CL_QUEUE_PRIORITY_KHR, CL_QUEUE_THROTTLE_KHR, cl_queue_properties_khr};
+        use opencl3::command_queue::CL_QUEUE_PROPERTIES;
+
+        let info = self.device.info()?;
+        let mut props: Vec<cl_queue_properties_khr> = vec![
+            CL_QUEUE_PROPERTIES as cl_queue_properties_khr,
+            self.properties(),
+        ];
+        if let Some(priority) = self.priority {
+            if !info.has_extension("cl_khr_priority_hints") {
+                return Err(Error::Msg(format!(
+                    "device {} does not support cl_khr_priority_hints",
+                    info.name
+                )));
+            }
+            props.extend([CL_QUEUE_PRIORITY_KHR, priority.raw()]);
+        }
+        if let Some(throttle) = self.throttle {
+            if !info.has_extension("cl_khr_throttle_hints") {
+                return Err(Error::Msg(format!(
+                    "device {} does not support cl_khr_throttle_hints",
+                    info.name
+                )));
+            }
+            props.extend([CL_QUEUE_THROTTLE_KHR, throttle.raw()]);
+        }
+        props.push(0);
+
+        let q = CLQueue::create_with_properties_khr(self.ctx.raw(), self.device.id(), &props)
+            .op(|| format!("create queue with hints {props:?}"))?;
+        Ok(Queue::new(q, self.device.id()))
+    }
+}
@@ -0,0 +1,147 @@
+//! Typed kernel signatures.
+//!
+//! A `Kernel<'brand, (Buf<f32>, Buf<f32>, u32)>` can only be launched with
+//! exactly `(DeviceBuffer<'brand, f32, Written>, DeviceBuffer<'brand, f32,
+//! Written>, u32)`. `Kernel::typed` checks the signature against the
+//! kernel's parameter count and, where the driver provides it, against
+//! `clGetKernelArgInfo` (address space and type name).
+
+use std::marker::PhantomData;
+
+use super::kernel_args::{KernelArg, KernelArgs};
+use super::{KernelArgInfo, KernelScalar, LocalMem};
+use crate::api::DeviceBuffer;
+use crate::api::opencl::kernel_info::AddressQualifier;
+use crate::buffer::state::Written;
+
+/// Signature of a kernel created with `Kernel::from_source`: any argument
+/// list is accepted and only the count is checked at launch.
+#[derive(Debug)]
+pub struct Untyped;
+
+/// `__global T*` (or `__constant T*`) parameter.
+#[derive(Debug)]
+pub struct Buf<T>(PhantomData<fn() -> T>);
+
+//#####
+// ARGUMENT TYPES
+//#####
+
+/// Type-level description of one kernel parameter.
+pub trait ArgType {
+    /// Value passed for this parameter at launch.
+    type Arg<'brand>: KernelArg<'brand>;
+
+    /// Expected parameter in OpenCL C notation, for error messages.
+    fn describe() -> String;
+
+    fn matches(info: &KernelArgInfo) -> bool;
+}
+
+impl<T: KernelScalar> ArgType for Buf<T> {
+    type Arg<'brand> = DeviceBuffer<'brand, T, Written>;
+
+    fn describe() -> String {
+        format!("__global {}*", T::CL_TYPE.unwrap_or("?"))
+    }
+
+    fn matches(info: &KernelArgInfo) -> bool {
+        matches!(
+            info.address,
+            AddressQualifier::Global | AddressQualifier::Constant
+        ) && T::CL_TYPE.is_none_or(|t| info.normalized_type() == format!("{t}*"))
+    }
+}
+
+impl<S: KernelScalar> ArgType for S {
+    type Arg<'brand> = S;
+
+    fn describe() -> String {
+        S::CL_TYPE.unwrap_or("?").to_owned()
+    }
+
+    fn matches(info: &KernelArgInfo) -> bool {
+        info.address == AddressQualifier::Private
+            && S::CL_TYPE.is_none_or(|t| info.normalized_type() == t)
+    }
+}
+
+/// `__local T*` parameter, passed as `LocalMem<T>`.
+impl<T: KernelScalar> ArgType for LocalMem<T> {
+    type Arg<'brand> = LocalMem<T>;
+
+    fn describe() -> String {
+        format!("__local {}*", T::CL_TYPE.unwrap_or("?"))
+    }
+
+    fn matches(info: &KernelArgInfo) -> bool {
+        info.address == AddressQualifier::Local
+            && T::CL_TYPE.is_none_or(|t| info.normalized_type() == format!("{t}*"))
+    }
+}
+
+//#####
+// SIGNATURES
+//#####
+
+/// A tuple of `ArgType`s describing all parameters of a kernel.
+pub trait KernelSignature {
+    /// Argument list accepted by `Kernel::launch`.
+    type Args<'brand>: KernelArgs<'brand>;
+
+    /// Number of parameters.
+    const LEN: u32;
+
+    /// Compare with the parameter metadata, one entry per parameter.
+    fn verify(infos: &[KernelArgInfo]) -> Result<(), String>;
+}
+
+fn verify_arg<A: ArgType>(index: usize, info: &KernelArgInfo) -> Result<(), String> {
+    if A::matches(info) {
+        return Ok(());
+    }
+    Err(format!(
+        "argument {index} `{}` is {:?} `{}`, signature expects `{}`",
+        info.name,
+        info.address,
+        info.type_name,
+        A::describe()
+    ))
+}
+
+macro_rules! impl_signature_tuple {
+    ($len:expr; $($name:ident),+) => {
+        impl<$($name: ArgType),+> KernelSignature for ($($name,)+) {
+            type Args<'brand> = ($($name::Arg<'brand>,)+);
+
+            const LEN: u32 = $len;
+
+            fn verify(infos: &[KernelArgInfo]) -> Result<(), String> {
+                let mut infos = infos.iter().enumerate();
+                $(
+                    if let Some((i, info)) = infos.next() {
+                        verify_arg::<$name>(i, info)?;
+                    }
+                )+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_signature_tuple!(1; A);
+impl_signature_tuple!(2; A, B);
+impl_signature_tuple!(3; A, B, C);
+impl_signature_tuple!(4; A, B, C, D);
+impl_signature_tuple!(5; A, B, C, D, E);
+impl_signature_tuple!(6; A, B, C, D, E, F);
+impl_signature_tuple!(7; A, B, C, D, E, F, G);
+impl_signature_tuple!(8; A, B, C, D, E, F, G, H);
+impl_signature_tuple!(9; A, B, C, D, E, F, G, H, I);
+impl_signature_tuple!(10; A, B, C, D, E, F, G, H, I, J);
+impl_signature_tuple!(11; A, B, C, D, E, F, G, H, I, J, K);
+impl_signature_tuple!(12; A, B, C, D, E, F, G, H, I, J, K, L);
+impl_signature_tuple!(13; A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_signature_tuple!(14; A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_signature_tuple!(15; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_signature_tuple!(16; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, Q);
@@ -0,0 +1,125 @@
+//! OpenCL vector types as kernel scalars.
+//!
+//! Host-side equivalents of `float4`, `int2`, ... with the layout of the
+//! `cl_float4` family: size and alignment are `n * size_of::<T>()`, and
+//! the 3-component types occupy the space of 4 components like `float3`
+//! does in OpenCL C. They can be passed by value, stored in buffers and
+//! used as fields of `#[derive(KernelScalar)]` structs.
+//!
+//! ```ignore
+//! use hpc_core::vector::Float4;
+//!
+//! let origin = Float4::new([0.0, 0.0, 0.0, 1.0]);
+//! kernel.launch(&queue, (points, origin), n)?;
+//! ```
+
+use bytemuck::{Pod, Zeroable};
+
+use super::KernelScalar;
+
+macro_rules! vector {
+    ($name:ident, $elem:ty, $cl:literal, $n:literal, $store:literal, $align:literal) => {
+        #[doc = concat!("OpenCL `", $cl, $n, "`, ", $align, "-byte aligned.")]
+        #[repr(C, align($align))]
+        #[derive(Debug, Clone, Copy, PartialEq, Default, Pod, Zeroable)]
+        pub struct $name([$elem; $store]);
+
+        impl $name {
+            pub const fn new(v: [$elem; $n]) -> Self {
+                let mut store = [0 as $elem; $store];
+                let mut i = 0;
+                while i < $n {
+                    store[i] = v[i];
+                    i += 1;
+                }
+                Self(store)
+            }
+
+            pub const fn to_array(self) -> [$elem; $n] {
+                let mut v = [0 as $elem; $n];
+                let mut i = 0;
+                while i < $n {
+                    v[i] = self.0[i];
+                    i += 1;
+                }
+                v
+            }
+        }
+
+        impl From<[$elem; $n]> for $name {
+            fn from(v: [$elem; $n]) -> Self {
+                Self::new(v)
+            }
+        }
+
+        impl From<$name> for [$elem; $n] {
+            fn from(v: $name) -> Self {
+                v.to_array()
+            }
+        }
+
+        impl KernelScalar for $name {
+            const CL_TYPE: Option<&'static str> = Some(concat!($cl, $n));
+        }
+
+        // Tabellenfehler fallen beim Kompilieren auf
+        const _: () = assert!(size_of::<$name>() == $align && align_of::<$name>() == $align);
+    };
+}
+
+// Ausrichtung = Größe; 3er-Vektoren belegen 4 Komponenten.
+// repr(align) braucht Literale, daher die Werte ausgeschrieben.
+macro_rules! vectors {
+    ($elem:ty, $cl:literal => $v2:ident $a2:literal, $v3:ident $a3:literal, $v4:ident $a4:literal,
+     $v8:ident $a8:literal, $v16:ident $a16:literal) => {
+        vector!($v2, $elem, $cl, 2, 2, $a2);
+        vector!($v3, $elem, $cl, 3, 4, $a3);
+        vector!($v4, $elem, $cl, 4, 4, $a4);
+        vector!($v8, $elem, $cl, 8, 8, $a8);
+        vector!($v16, $elem, $cl, 16, 16, $a16);
+    };
+}
+
+vectors!(i8, "char" => Char2 2, Char3 4, Char4 4, Char8 8, Char16 16);
+vectors!(u8, "uchar" => UChar2 2, UChar3 4, UChar4 4, UChar8 8, UChar16 16);
+vectors!(i16, "short" => Short2 4, Short3 8, Short4 8, Short8 16, Short16 32);
+vectors!(u16, "ushort" => UShort2 4, UShort3 8, UShort4 8, UShort8 16, UShort16 32);
+vectors!(i32, "int" => Int2 8, Int3 16, Int4 16, Int8 32, Int16 64);
+vectors!(u32, "uint" => UInt2 8, UInt3 16, UInt4 16, UInt8 32, UInt16 64);
+vectors!(i64, "long" => Long2 16, Long3 32, Long4 32, Long8 64, Long16 128);
+vectors!(u64, "ulong" => ULong2 16, ULong3 32, ULong4 32, ULong8 64, ULong16 128);
+vectors!(f32, "float" => Float2 8, Float3 16, Float4 16, Float8 32, Float16 64);
+vectors!(f64, "double" => Double2 16, Double3 32, Double4 32, Double8 64, Double16 128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_and_to_array_round_trip() {
+        let v = Float4::new([1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(v.to_array(), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(<[f32; 4]>::from(v), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(Int2::from([-1, 7]).to_array(), [-1, 7]);
+
+        let v16 = UChar16::new(std::array::from_fn(|i| i as u8));
+        assert_eq!(v16.to_array(), std::array::from_fn(|i| i as u8));
+    }
+
+    #[test]
+    fn three_component_vectors_pad_to_four() {
+        let v = Float3::new([1.0, 2.0, 3.0]);
+        assert_eq!(v.to_array(), [1.0, 2.0, 3.0]);
+        // Viertes Element ist Padding und bleibt 0
+        assert_eq!(bytemuck::cast::<Float3, [f32; 4]>(v), [1.0, 2.0, 3.0, 0.0]);
+        assert_eq!(size_of::<Float3>(), size_of::<Float4>());
+        assert_eq!(Float3::default().to_array(), [0.0; 3]);
+    }
+
+    #[test]
+    fn cl_type_names() {
+        assert_eq!(Float4::CL_TYPE, Some("float4"));
+        assert_eq!(UShort3::CL_TYPE, Some("ushort3"));
+        assert_eq!(Double16::CL_TYPE, Some("double16"));
+    }
+}
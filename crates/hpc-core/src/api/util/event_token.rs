@@ -2,27 +2,74 @@
 //! An EventToken represents the completion of an asynchronous
 //! operation (such as buffer writes, reads, or kernel launches).
 //! - `#[must_use]`: prevents silent dropping of event tokens.
+//! - Tokens from a `Queue<'brand, Profiling>` are `EventToken<'brand, Profiling>`
+//!   and additionally offer `wait_profiled`.
+//! - `status`/`try_wait` poll without blocking, so one host thread can
+//!   overlap CPU work with several in-flight operations.
 
-use crate::api::DeviceBuffer;
-use crate::buffer::GpuEventGuard;
-use crate::buffer::state::InFlight;
-use crate::buffer::state::Written;
+use super::ProfileInfo;
+use crate::api::{CompleteToken, InFlightBuffers, NoProfiling, Profiling, ProfilingMode};
+use crate::buffer::{GpuEventGuard, wait_checked};
+use crate::error::{Error, Result};
 use core::marker::PhantomData;
-use opencl3::event::Event;
+use opencl3::error_codes::ClError;
+use opencl3::event::{CL_COMPLETE, CL_QUEUED, CL_RUNNING, CL_SUBMITTED, Event};
+use opencl3::types::cl_int;
+
+//#####
+// STATUS
+//#####
+
+/// `CL_EVENT_COMMAND_EXECUTION_STATUS` of a command that has not failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventStatus {
+    Queued,
+    Submitted,
+    Running,
+    Complete,
+}
+
+/// Result of a non-blocking `try_wait`.
+///
+/// `W` is the buffer (set) in state `Written`, `G` the token type that is
+/// handed back while the command is still running (`EventToken` or
+/// `ReadGuard`) and `B` the in-flight buffer (set).
+#[must_use = "a pending operation still has to be waited on"]
+pub enum TryWait<W, G, B> {
+    /// The command finished; the buffers are usable again.
+    Ready(W),
+    /// Still running; token and buffers are returned unchanged.
+    Pending(G, B),
+}
+
+fn event_status(code: cl_int) -> Result<EventStatus> {
+    match code {
+        CL_COMPLETE => Ok(EventStatus::Complete),
+        CL_RUNNING => Ok(EventStatus::Running),
+        CL_SUBMITTED => Ok(EventStatus::Submitted),
+        CL_QUEUED => Ok(EventStatus::Queued),
+        code => Err(Error::OpenCl(ClError(code))),
+    }
+}
+
+//#####
+// TOKEN
+//#####
 
 #[must_use = "GPU work is in-flight: call wait(event, buf) to complete it"]
-pub struct EventToken<'brand> {
+pub struct EventToken<'brand, P: ProfilingMode = NoProfiling> {
     inner: GpuEventGuard,
-    _brand: PhantomData<&'brand ()>,
+    _brand: PhantomData<fn(&'brand ()) -> &'brand ()>,
+    _profiling: PhantomData<P>,
 }
 
-impl<'brand> EventToken<'brand> {
+impl<'brand, P: ProfilingMode> EventToken<'brand, P> {
     /// Creates a token directly from a guard (low-level).
-
     pub(crate) fn from_guard(guard: GpuEventGuard) -> Self {
         Self {
             inner: guard,
             _brand: PhantomData,
+            _profiling: PhantomData,
         }
     }
 
@@ -32,19 +79,94 @@ impl<'brand> EventToken<'brand> {
     }
 
     /// Consuming transition: only allowed path from InFlight → Ready.
-    pub fn wait<T>(
+    ///
+    /// `bufs` is a single buffer or the whole set returned by
+    /// `Kernel::launch`. Fails with `Error::CommandFailed` if the device
+    /// aborted the command; the buffer contents would be undefined, so
+    /// they are not handed back.
+    pub fn wait<B: InFlightBuffers<'brand>>(self, bufs: B) -> Result<B::Written> {
+        self.inner.wait()?;
+        Ok(bufs.complete(CompleteToken(())))
+    }
+
+    /// Query the execution status without blocking.
+    ///
+    /// A negative status (the command was aborted by the device) is
+    /// returned as `Error::OpenCl`.
+    pub fn status(&self) -> Result<EventStatus> {
+        event_status(self.inner.event().command_execution_status()?.0)
+    }
+
+    /// Non-blocking variant of `wait`: returns the `Written` buffers if the
+    /// command has completed, otherwise hands back token and buffers.
+    pub fn try_wait<B: InFlightBuffers<'brand>>(
         self,
-        buf: DeviceBuffer<'brand, T, InFlight>,
-    ) -> DeviceBuffer<'brand, T, Written> {
-        self.inner.wait();
-
-        DeviceBuffer::from_inner(
-            crate::buffer::GpuBuffer {
-                buf: buf.inner.buf,
-                len_bytes: buf.inner.len_bytes,
-                _state: PhantomData::<Written>,
-            },
-            buf.len,
-        )
+        bufs: B,
+    ) -> Result<TryWait<B::Written, Self, B>> {
+        let (token, status) = self.poll()?;
+        if status != EventStatus::Complete {
+            return Ok(TryWait::Pending(token, bufs));
+        }
+        // Already complete, so this does not block
+        Ok(TryWait::Ready(token.wait(bufs)?))
+    }
+
+    /// `status` for `try_wait`. On failure the event is given up, so the
+    /// error reaches the caller once and is not logged again by Drop.
+    fn poll(self) -> Result<(Self, EventStatus)> {
+        let code = match self.inner.event().command_execution_status() {
+            Ok(code) => code.0,
+            Err(e) => {
+                self.inner.forget();
+                return Err(e.into());
+            }
+        };
+        match event_status(code) {
+            Ok(status) => Ok((self, status)),
+            Err(e) => {
+                self.inner.release();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<'brand> EventToken<'brand, Profiling> {
+    /// Like `wait`, but also returns the device timestamps of the command.
+    ///
+    /// Only available for tokens of a profiling queue; the driver does not
+    /// record timestamps otherwise.
+    pub fn wait_profiled<B: InFlightBuffers<'brand>>(
+        self,
+        bufs: B,
+    ) -> Result<(B::Written, ProfileInfo)> {
+        let evt = self.inner.into_event();
+        wait_checked(&evt)?;
+        let info = ProfileInfo::query(&evt)?;
+        Ok((bufs.complete(CompleteToken(())), info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::{dropped_errors_test_lock, take_dropped_errors};
+
+    #[test]
+    fn failed_poll_is_not_logged_again_on_drop() {
+        let _lock = dropped_errors_test_lock();
+        take_dropped_errors();
+
+        // Ohne Gerät schlägt schon die Statusabfrage fehl
+        let token = EventToken::<NoProfiling>::from_event(Event::new(std::ptr::null_mut()));
+        assert!(token.poll().is_err());
+        assert!(take_dropped_errors().is_empty());
+    }
+
+    #[test]
+    fn maps_status_codes() {
+        assert_eq!(event_status(CL_COMPLETE).unwrap(), EventStatus::Complete);
+        assert_eq!(event_status(CL_QUEUED).unwrap(), EventStatus::Queued);
+        assert!(matches!(event_status(-5), Err(Error::OpenCl(ClError(-5)))));
     }
 }
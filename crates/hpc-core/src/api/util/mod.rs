@@ -5,8 +5,12 @@
 
 mod event_token;
 mod map_token;
+mod profile_info;
 mod read_guard;
+mod write_guard;
 
-pub use event_token::EventToken;
+pub use event_token::{EventStatus, EventToken, TryWait};
 pub use map_token::MapToken;
+pub use profile_info::ProfileInfo;
 pub use read_guard::ReadGuard;
+pub use write_guard::WriteGuard;
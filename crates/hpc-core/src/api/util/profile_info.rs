@@ -0,0 +1,53 @@
+//! Device timestamps of a finished command (`clGetEventProfilingInfo`).
+//!
+//! Only obtainable from an `EventToken<'brand, Profiling>`, i.e. for
+//! commands enqueued on a queue built with `QueueBuilder::profiling`.
+
+use opencl3::event::Event;
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::error::Result;
+
+/// Timestamps in nanoseconds of the device clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ProfileInfo {
+    /// `CL_PROFILING_COMMAND_QUEUED`: command was enqueued by the host.
+    pub queued: u64,
+    /// `CL_PROFILING_COMMAND_SUBMIT`: command was submitted to the device.
+    pub submit: u64,
+    /// `CL_PROFILING_COMMAND_START`: execution started.
+    pub start: u64,
+    /// `CL_PROFILING_COMMAND_END`: execution finished.
+    pub end: u64,
+    /// `CL_PROFILING_COMMAND_COMPLETE` (OpenCL 2.0+); `None` on older devices.
+    pub complete: Option<u64>,
+}
+
+impl ProfileInfo {
+    /// Read all timestamps of a completed event.
+    pub(crate) fn query(evt: &Event) -> Result<Self> {
+        Ok(Self {
+            queued: evt.profiling_command_queued()?,
+            submit: evt.profiling_command_submit()?,
+            start: evt.profiling_command_start()?,
+            end: evt.profiling_command_end()?,
+            complete: evt.profiling_command_complete().ok(),
+        })
+    }
+
+    /// Pure execution time on the device (`end - start`).
+    pub fn execution(&self) -> Duration {
+        Duration::from_nanos(self.end.saturating_sub(self.start))
+    }
+
+    /// Time the command waited before execution started (`start - queued`).
+    pub fn latency(&self) -> Duration {
+        Duration::from_nanos(self.start.saturating_sub(self.queued))
+    }
+
+    /// Time from enqueue to end of execution (`end - queued`).
+    pub fn total(&self) -> Duration {
+        Duration::from_nanos(self.end.saturating_sub(self.queued))
+    }
+}
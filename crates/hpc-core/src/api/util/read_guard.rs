@@ -1,25 +1,60 @@
-use super::EventToken;
-use crate::api::DeviceBuffer;
+use super::{EventStatus, EventToken, ProfileInfo, TryWait};
+use crate::api::{DeviceBuffer, NoProfiling, Profiling, ProfilingMode};
 use crate::buffer::state::{InFlight, Written};
+use crate::error::Result;
+
+type ReadTryWait<'a, 'brand, T, P> = TryWait<
+    DeviceBuffer<'brand, T, Written>,
+    ReadGuard<'a, 'brand, T, P>,
+    DeviceBuffer<'brand, T, InFlight>,
+>;
 
 /// Guard that holds a mutable slice until GPU read operation completes
 #[must_use]
-pub struct ReadGuard<'a, 'brand, T> {
+pub struct ReadGuard<'a, 'brand, T, P: ProfilingMode = NoProfiling> {
     slice: &'a mut [T],
-    token: EventToken<'brand>,
+    token: EventToken<'brand, P>,
 }
 
-impl<'a, 'brand, T> ReadGuard<'a, 'brand, T> {
+impl<'a, 'brand, T, P: ProfilingMode> ReadGuard<'a, 'brand, T, P> {
     /// Create new ReadGuard (internal use only)
-    pub(crate) fn new(slice: &'a mut [T], token: EventToken<'brand>) -> Self {
+    pub(crate) fn new(slice: &'a mut [T], token: EventToken<'brand, P>) -> Self {
         Self { slice, token }
     }
 
     /// Wait until GPU is finished, then return Ready buffer and release the slice
-    #[must_use]
-    pub fn wait(self, buf: DeviceBuffer<'brand, T, InFlight>) -> DeviceBuffer<'brand, T, Written> {
-        let buf_ready = self.token.wait(buf);
-        buf_ready
+    pub fn wait(
+        self,
+        buf: DeviceBuffer<'brand, T, InFlight>,
+    ) -> Result<DeviceBuffer<'brand, T, Written>> {
+        self.token.wait(buf)
+    }
+
+    /// Execution status of the read, see `EventToken::status`.
+    pub fn status(&self) -> Result<EventStatus> {
+        self.token.status()
+    }
+
+    /// Non-blocking `wait`: while the read is running the guard keeps
+    /// borrowing the slice and is handed back together with the buffer.
+    pub fn try_wait(
+        self,
+        buf: DeviceBuffer<'brand, T, InFlight>,
+    ) -> Result<ReadTryWait<'a, 'brand, T, P>> {
+        if self.token.status()? != EventStatus::Complete {
+            return Ok(TryWait::Pending(self, buf));
+        }
+        Ok(TryWait::Ready(self.wait(buf)?))
+    }
+}
+
+impl<'a, 'brand, T> ReadGuard<'a, 'brand, T, Profiling> {
+    /// Like `wait`, but also returns the timestamps of the transfer.
+    pub fn wait_profiled(
+        self,
+        buf: DeviceBuffer<'brand, T, InFlight>,
+    ) -> Result<(DeviceBuffer<'brand, T, Written>, ProfileInfo)> {
+        self.token.wait_profiled(buf)
     }
 }
 
@@ -27,7 +62,7 @@ impl<'a, 'brand, T> ReadGuard<'a, 'brand, T> {
 // The data is only valid after wait()
 
 // Optional: Debug implementation
-impl<'a, 'brand, T> std::fmt::Debug for ReadGuard<'a, 'brand, T> {
+impl<'a, 'brand, T, P: ProfilingMode> std::fmt::Debug for ReadGuard<'a, 'brand, T, P> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ReadGuard")
             .field("slice_len", &self.slice.len())
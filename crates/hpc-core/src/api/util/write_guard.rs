@@ -0,0 +1,70 @@
+use super::{EventStatus, EventToken, ProfileInfo, TryWait};
+use crate::api::{DeviceBuffer, NoProfiling, Profiling, ProfilingMode};
+use crate::buffer::state::{InFlight, Written};
+use crate::error::Result;
+
+type WriteTryWait<'a, 'brand, T, P> = TryWait<
+    DeviceBuffer<'brand, T, Written>,
+    WriteGuard<'a, 'brand, T, P>,
+    DeviceBuffer<'brand, T, InFlight>,
+>;
+
+/// Guard that borrows the source slice until a GPU write operation completes
+///
+/// The driver reads the host memory while the write is running, so the
+/// slice must neither change nor be freed before then.
+#[must_use]
+pub struct WriteGuard<'a, 'brand, T, P: ProfilingMode = NoProfiling> {
+    slice: &'a [T],
+    token: EventToken<'brand, P>,
+}
+
+impl<'a, 'brand, T, P: ProfilingMode> WriteGuard<'a, 'brand, T, P> {
+    /// Create new WriteGuard (internal use only)
+    pub(crate) fn new(slice: &'a [T], token: EventToken<'brand, P>) -> Self {
+        Self { slice, token }
+    }
+
+    /// Wait until GPU is finished, then return Written buffer and release the slice
+    pub fn wait(
+        self,
+        buf: DeviceBuffer<'brand, T, InFlight>,
+    ) -> Result<DeviceBuffer<'brand, T, Written>> {
+        self.token.wait(buf)
+    }
+
+    /// Execution status of the write, see `EventToken::status`.
+    pub fn status(&self) -> Result<EventStatus> {
+        self.token.status()
+    }
+
+    /// Non-blocking `wait`: while the write is running the guard keeps
+    /// borrowing the slice and is handed back together with the buffer.
+    pub fn try_wait(
+        self,
+        buf: DeviceBuffer<'brand, T, InFlight>,
+    ) -> Result<WriteTryWait<'a, 'brand, T, P>> {
+        if self.token.status()? != EventStatus::Complete {
+            return Ok(TryWait::Pending(self, buf));
+        }
+        Ok(TryWait::Ready(self.wait(buf)?))
+    }
+}
+
+impl<'a, 'brand, T> WriteGuard<'a, 'brand, T, Profiling> {
+    /// Like `wait`, but also returns the timestamps of the transfer.
+    pub fn wait_profiled(
+        self,
+        buf: DeviceBuffer<'brand, T, InFlight>,
+    ) -> Result<(DeviceBuffer<'brand, T, Written>, ProfileInfo)> {
+        self.token.wait_profiled(buf)
+    }
+}
+
+impl<'a, 'brand, T, P: ProfilingMode> std::fmt::Debug for WriteGuard<'a, 'brand, T, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriteGuard")
+            .field("slice_len", &self.slice.len())
+            .finish()
+    }
+}
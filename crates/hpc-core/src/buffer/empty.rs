@@ -5,7 +5,7 @@
 use crate::buffer::GpuBuffer;
 use crate::buffer::MapGuard;
 use crate::buffer::state::{Empty, Mapped, Written};
-use crate::error::{Error, Result};
+use crate::error::{Error, Result, ResultExt};
 use core::mem::size_of;
 use opencl3::command_queue::CommandQueue;
 use opencl3::memory::CL_MEM_READ_WRITE;
@@ -26,7 +26,7 @@ impl GpuBuffer<Empty> {
             n_bytes,
             core::ptr::null_mut(),
         )
-        .map_err(Error::from)?;
+        .op(|| format!("allocate buffer of {n_bytes} bytes"))?;
 
         Ok(Self {
             buf: cl_buf,
@@ -45,7 +45,9 @@ impl GpuBuffer<Empty> {
             });
         }
 
-        let _evt = queue.enqueue_write_buffer(&mut self.buf, CL_BLOCKING, 0, host, &[])?;
+        let _evt = queue
+            .enqueue_write_buffer(&mut self.buf, CL_BLOCKING, 0, host, &[])
+            .op(|| format!("blocking write of {} bytes", host.len()))?;
 
         Ok(GpuBuffer {
             buf: self.buf,
@@ -56,22 +58,23 @@ impl GpuBuffer<Empty> {
 
     /// Maps the buffer on the host side → Mapped.
     /// Returns a guard that gives you `&mut [T]`.
-
     pub fn map_for_write_block(
-        mut self,
+        self,
         queue: &CommandQueue,
     ) -> Result<(GpuBuffer<Mapped>, MapGuard<'_>)> {
         let mut mapped_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
 
-        let _event = queue.enqueue_map_buffer(
-            &mut self.buf,
-            opencl3::types::CL_TRUE, // blocking
-            CL_MEM_READ_WRITE,
-            0,
-            self.len_bytes,
-            &mut mapped_ptr,
-            &[],
-        )?;
+        let _event = queue
+            .enqueue_map_buffer(
+                &self.buf,
+                opencl3::types::CL_TRUE, // blocking
+                CL_MEM_READ_WRITE,
+                0,
+                self.len_bytes,
+                &mut mapped_ptr,
+                &[],
+            )
+            .op(|| format!("map buffer of {} bytes", self.len_bytes))?;
 
         let guard = MapGuard::new(queue, self.buf.get(), mapped_ptr as *mut u8);
 
@@ -1,6 +1,49 @@
 //! Event guard for automatic synchronization
 
-use opencl3::event::Event;
+use opencl3::error_codes::ClError;
+use opencl3::event::{Event, command_type_text};
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::error::{Error, Result};
+
+/// Höchstens so viele Fehler werden gepuffert, ältere fallen heraus.
+const DROPPED_ERRORS_CAP: usize = 64;
+
+/// Errors of events that were only finished by `Drop` (see `take_dropped_errors`).
+static DROPPED_ERRORS: Mutex<DroppedErrors> = Mutex::new(DroppedErrors {
+    errors: VecDeque::new(),
+    discarded: 0,
+});
+
+/// The last `DROPPED_ERRORS_CAP` errors plus the number pushed out before them.
+struct DroppedErrors {
+    errors: VecDeque<Error>,
+    discarded: usize,
+}
+
+/// Tests, die das geteilte Log prüfen, laufen nacheinander.
+#[cfg(test)]
+pub(crate) fn dropped_errors_test_lock() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn dropped_errors() -> MutexGuard<'static, DroppedErrors> {
+    DROPPED_ERRORS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Nur auf dem Fehlerpfad, der normale Drop nimmt keinen Lock
+fn record_dropped_error(e: Error) {
+    let mut dropped = dropped_errors();
+    if dropped.errors.len() == DROPPED_ERRORS_CAP {
+        dropped.errors.pop_front();
+        dropped.discarded += 1;
+    }
+    dropped.errors.push_back(e);
+}
 
 /// Guard that waits for event completion on drop
 pub struct GpuEventGuard {
@@ -24,20 +67,37 @@ impl GpuEventGuard {
         self.evt.as_ref().expect("no event")
     }
 
+    /// Give up the event without waiting on it; Drop records nothing.
+    /// For error paths whose failure already goes to the caller.
+    pub(crate) fn release(mut self) {
+        drop(self.evt.take());
+    }
+
+    /// Like `release`, but also skips `clReleaseEvent`: for a handle whose
+    /// status query failed and that may not be valid anymore.
+    pub(crate) fn forget(mut self) {
+        std::mem::forget(self.evt.take());
+    }
+
     /// Explicitly wait for event completion (consuming).
     /// After this returns, Drop won't wait again.
+    ///
+    /// Fails with `Error::CommandFailed` if the device aborted the command.
     #[inline]
-    pub fn wait(mut self) {
-        if let Some(e) = self.evt.take() {
-            let _ = e.wait();
+    pub fn wait(mut self) -> Result<()> {
+        match self.evt.take() {
+            Some(e) => wait_checked(&e),
+            None => Ok(()),
         }
     }
 }
 
 impl Drop for GpuEventGuard {
     fn drop(&mut self) {
-        if let Some(evt) = self.evt.take() {
-            let _ = evt.wait();
+        if let Some(evt) = self.evt.take()
+            && let Err(e) = wait_checked(&evt)
+        {
+            record_dropped_error(e);
         }
     }
 }
@@ -48,3 +108,75 @@ impl From<Event> for GpuEventGuard {
         GpuEventGuard::new(evt)
     }
 }
+
+/// Wait for `evt` and check its execution status.
+///
+/// For a failed command `clWaitForEvents` only reports
+/// `CL_EXEC_STATUS_ERROR_FOR_EVENTS_IN_WAIT_LIST`; the actual error code
+/// is the negative `CL_EVENT_COMMAND_EXECUTION_STATUS`.
+pub(crate) fn wait_checked(evt: &Event) -> Result<()> {
+    let waited = evt.wait();
+
+    let status = evt.command_execution_status()?.0;
+    if status < 0 {
+        let command = evt
+            .command_type()
+            .map_or("unknown command", |t| command_type_text(t.0));
+        return Err(Error::CommandFailed {
+            command,
+            code: ClError(status),
+        });
+    }
+
+    Ok(waited?)
+}
+
+/// Take all errors of commands whose event guard was dropped without an
+/// explicit `wait` (e.g. a discarded `EventToken`).
+///
+/// `Drop` cannot return an error, so failures are recorded here instead of
+/// being silently lost.
+///
+/// The log is process-wide and shared by all contexts and threads. It keeps
+/// only the 64 most recent errors; older ones are counted by
+/// `discarded_dropped_errors` instead. Programs that drop tokens should
+/// drain it regularly, e.g. after each batch of work.
+pub fn take_dropped_errors() -> Vec<Error> {
+    let mut dropped = dropped_errors();
+    dropped.discarded = 0;
+    dropped.errors.drain(..).collect()
+}
+
+/// Number of dropped-guard errors that were pushed out of the full log
+/// since the last `take_dropped_errors`.
+pub fn discarded_dropped_errors() -> usize {
+    dropped_errors().discarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_and_takes_with_cap() {
+        let _lock = dropped_errors_test_lock();
+        take_dropped_errors();
+
+        record_dropped_error(Error::Msg("first".into()));
+        record_dropped_error(Error::Msg("second".into()));
+        let taken = take_dropped_errors();
+        assert!(
+            matches!(&taken[..], [Error::Msg(a), Error::Msg(b)] if a == "first" && b == "second")
+        );
+        assert!(take_dropped_errors().is_empty());
+
+        for i in 0..DROPPED_ERRORS_CAP + 3 {
+            record_dropped_error(Error::Msg(i.to_string()));
+        }
+        assert_eq!(discarded_dropped_errors(), 3);
+        let taken = take_dropped_errors();
+        assert_eq!(taken.len(), DROPPED_ERRORS_CAP);
+        assert!(matches!(&taken[0], Error::Msg(m) if m == "3"));
+        assert_eq!(discarded_dropped_errors(), 0);
+    }
+}
@@ -3,15 +3,21 @@
 mod gpu_guard;
 mod map_guard;
 
-pub use gpu_guard::GpuEventGuard;
+#[cfg(test)]
+pub(crate) use gpu_guard::dropped_errors_test_lock;
+pub(crate) use gpu_guard::wait_checked;
+pub use gpu_guard::{GpuEventGuard, discarded_dropped_errors, take_dropped_errors};
 pub use map_guard::MapGuard;
 pub mod state;
-pub use state::{InFlight, Mapped, State};
+pub use state::{InFlight, Mapped, Overwritable, State};
 
 mod empty;
 mod inflight;
 mod written;
 
+use crate::error::{Error, Result, ResultExt};
+use opencl3::command_queue::CommandQueue;
+use opencl3::event::Event;
 use opencl3::memory::Buffer;
 
 #[derive(Debug)]
@@ -50,3 +56,70 @@ impl<S: State> GpuBuffer<S> {
         self.len_bytes()
     }
 }
+
+impl<S: Overwritable> GpuBuffer<S> {
+    /// Fill the whole buffer with `pattern` (`clEnqueueFillBuffer`).
+    ///
+    /// OpenCL only accepts patterns of 1, 2, 4, ..., 128 bytes that divide
+    /// the buffer size.
+    pub fn enqueue_fill(
+        mut self,
+        queue: &CommandQueue,
+        pattern: &[u8],
+    ) -> Result<(GpuBuffer<InFlight>, Event)> {
+        check_fill_pattern(pattern.len(), self.len_bytes)?;
+
+        let evt = queue
+            .enqueue_fill_buffer(&mut self.buf, pattern, 0, self.len_bytes, &[])
+            .op(|| {
+                format!(
+                    "fill of {} bytes with a {}-byte pattern",
+                    self.len_bytes,
+                    pattern.len()
+                )
+            })?;
+
+        Ok((
+            GpuBuffer {
+                buf: self.buf,
+                len_bytes: self.len_bytes,
+                _state: core::marker::PhantomData::<InFlight>,
+            },
+            evt,
+        ))
+    }
+}
+
+/// Pattern size rules of `clEnqueueFillBuffer`, checked before the call.
+fn check_fill_pattern(pattern_len: usize, len_bytes: usize) -> Result<()> {
+    if !pattern_len.is_power_of_two() || pattern_len > 128 {
+        return Err(Error::Msg(format!(
+            "fill pattern of {pattern_len} bytes: OpenCL needs a power of two up to 128"
+        )));
+    }
+    if !len_bytes.is_multiple_of(pattern_len) {
+        return Err(Error::Msg(format!(
+            "fill pattern of {pattern_len} bytes does not divide the buffer size of {len_bytes} bytes"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_pattern_sizes() {
+        for len in [1, 2, 4, 8, 16, 32, 64, 128] {
+            assert!(check_fill_pattern(len, 1024).is_ok(), "{len}");
+        }
+        for len in [0, 3, 12, 256] {
+            assert!(
+                matches!(check_fill_pattern(len, 3072), Err(Error::Msg(_))),
+                "{len}"
+            );
+        }
+        assert!(check_fill_pattern(8, 12).is_err());
+    }
+}
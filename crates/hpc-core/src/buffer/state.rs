@@ -15,12 +15,17 @@ pub struct Empty;
 impl sealed::Sealed for Empty {}
 impl State for Empty {}
 
-
 #[derive(Debug, Clone, Copy)]
 pub struct Written;
 impl sealed::Sealed for Written {}
 impl State for Written {}
 
+/// States whose contents may be replaced by a device-side command (copy,
+/// fill) without reading them first.
+pub trait Overwritable: State {}
+impl Overwritable for Empty {}
+impl Overwritable for Written {}
+
 /// Buffer is mapped for host access (memory-mapped).
 #[derive(Debug, Clone, Copy)]
 pub struct Mapped;
@@ -37,4 +42,4 @@ impl State for InFlight {}
 #[derive(Debug, Clone, Copy)]
 pub struct Synchronized;
 impl sealed::Sealed for Synchronized {}
-impl State for Synchronized {}
\ No newline at end of file
+impl State for Synchronized {}
@@ -4,10 +4,11 @@
 //! synchronized with the device. This state exists to prevent accidental
 //! reuse of stale data on the GPU.
 
+use crate::api::{NdRange, RectBytes};
 use crate::buffer::GpuBuffer;
 use crate::buffer::MapGuard;
-use crate::buffer::state::{InFlight, Mapped, Written};
-use crate::error::{Error, Result};
+use crate::buffer::state::{InFlight, Mapped, Overwritable, Written};
+use crate::error::{Error, Result, ResultExt};
 use opencl3::command_queue::CommandQueue;
 use opencl3::event::Event;
 use opencl3::memory::CL_MEM_READ_WRITE;
@@ -15,7 +16,6 @@ use opencl3::memory::ClMem; // <-- Das fehlt!
 use opencl3::types::CL_BLOCKING;
 use opencl3::types::CL_NON_BLOCKING;
 use opencl3::types::cl_bool;
-use std::ffi::c_void;
 use std::marker::PhantomData;
 
 impl GpuBuffer<Written> {
@@ -32,7 +32,9 @@ impl GpuBuffer<Written> {
             });
         }
 
-        let evt = queue.enqueue_write_buffer(&mut self.buf, blocking, 0, host, &[])?;
+        let evt = queue
+            .enqueue_write_buffer(&mut self.buf, blocking, 0, host, &[])
+            .op(|| format!("write of {} bytes", host.len()))?;
 
         Ok(evt)
     }
@@ -46,7 +48,9 @@ impl GpuBuffer<Written> {
         }
 
         // Write enqueuen
-        let _evt = queue.enqueue_write_buffer(&mut self.buf, CL_BLOCKING, 0, host, &[])?;
+        let _evt = queue
+            .enqueue_write_buffer(&mut self.buf, CL_BLOCKING, 0, host, &[])
+            .op(|| format!("blocking write of {} bytes", host.len()))?;
 
         Ok(GpuBuffer {
             buf: self.buf,
@@ -60,7 +64,9 @@ impl GpuBuffer<Written> {
         queue: &CommandQueue,
         host: &[u8],
     ) -> Result<(GpuBuffer<InFlight>, Event)> {
-        let evt = queue.enqueue_write_buffer(&mut self.buf, CL_NON_BLOCKING, 0, host, &[])?;
+        let evt = queue
+            .enqueue_write_buffer(&mut self.buf, CL_NON_BLOCKING, 0, host, &[])
+            .op(|| format!("non-blocking write of {} bytes", host.len()))?;
         Ok((
             GpuBuffer {
                 buf: self.buf, // Move ist OK, da self konsumiert wird
@@ -84,7 +90,9 @@ impl GpuBuffer<Written> {
             });
         }
 
-        let evt = queue.enqueue_read_buffer(&self.buf, blocking, 0, host, &[])?;
+        let evt = queue
+            .enqueue_read_buffer(&self.buf, blocking, 0, host, &[])
+            .op(|| format!("read of {} bytes", host.len()))?;
 
         Ok(evt)
     }
@@ -102,7 +110,9 @@ impl GpuBuffer<Written> {
             });
         }
 
-        let evt = queue.enqueue_read_buffer(&self.buf, blocking, 0, host, &[])?;
+        let evt = queue
+            .enqueue_read_buffer(&self.buf, blocking, 0, host, &[])
+            .op(|| format!("read of {} bytes", host.len()))?;
 
         Ok((
             GpuBuffer {
@@ -114,21 +124,62 @@ impl GpuBuffer<Written> {
         ))
     }
 
+    /// Read `host.len()` bytes starting at byte `offset`.
+    pub fn enqueue_read_at(
+        &self,
+        queue: &CommandQueue,
+        offset: usize,
+        host: &mut [u8],
+        blocking: cl_bool,
+    ) -> Result<Event> {
+        let evt = queue
+            .enqueue_read_buffer(&self.buf, blocking, offset, host, &[])
+            .op(|| format!("read of {} bytes at offset {offset}", host.len()))?;
+
+        Ok(evt)
+    }
+
+    /// Write `host` starting at byte `offset`.
+    pub fn enqueue_write_at(
+        &mut self,
+        queue: &CommandQueue,
+        offset: usize,
+        host: &[u8],
+        blocking: cl_bool,
+    ) -> Result<Event> {
+        let evt = queue
+            .enqueue_write_buffer(&mut self.buf, blocking, offset, host, &[])
+            .op(|| format!("write of {} bytes at offset {offset}", host.len()))?;
+
+        Ok(evt)
+    }
+
+    /// State change after a non-blocking command was enqueued on the buffer.
+    pub(crate) fn into_inflight(self) -> GpuBuffer<InFlight> {
+        GpuBuffer {
+            buf: self.buf,
+            len_bytes: self.len_bytes,
+            _state: PhantomData::<InFlight>,
+        }
+    }
+
     pub fn map_for_write_block(
-        mut self,
+        self,
         queue: &CommandQueue,
     ) -> Result<(GpuBuffer<Mapped>, MapGuard<'_>)> {
         let mut mapped_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
 
-        let _event = queue.enqueue_map_buffer(
-            &mut self.buf,
-            opencl3::types::CL_TRUE, // blocking
-            CL_MEM_READ_WRITE,
-            0,
-            self.len_bytes,
-            &mut mapped_ptr,
-            &[],
-        )?;
+        let _event = queue
+            .enqueue_map_buffer(
+                &self.buf,
+                opencl3::types::CL_TRUE, // blocking
+                CL_MEM_READ_WRITE,
+                0,
+                self.len_bytes,
+                &mut mapped_ptr,
+                &[],
+            )
+            .op(|| format!("map buffer of {} bytes", self.len_bytes))?;
 
         let guard = MapGuard::new(queue, self.buf.get(), mapped_ptr as *mut u8);
 
@@ -142,22 +193,39 @@ impl GpuBuffer<Written> {
         ))
     }
 
-    pub fn enqueue_kernel(
+    pub fn enqueue_kernel<const D: usize>(
         self,
         queue: &CommandQueue,
         kernel: &opencl3::kernel::Kernel,
-        global_work_size: usize,
+        range: &NdRange<D>,
     ) -> Result<(GpuBuffer<InFlight>, Event)> {
-        let kernel_ptr = kernel.get() as *mut c_void;
+        let evt = range.enqueue(queue, kernel)?;
+
+        Ok((
+            GpuBuffer {
+                buf: self.buf,
+                len_bytes: self.len_bytes,
+                _state: PhantomData::<InFlight>,
+            },
+            evt,
+        ))
+    }
 
-        let evt = queue.enqueue_nd_range_kernel(
-            kernel_ptr,
-            1, // 1D NDRange (später anpassen)
-            std::ptr::null(),
-            &global_work_size as *const usize,
-            std::ptr::null(),
-            &[],
-        )?;
+    /// Device-side copy of `size` bytes into `dst` (`clEnqueueCopyBuffer`).
+    /// Both buffers are in flight until the event completes.
+    pub fn enqueue_copy<S: Overwritable>(
+        self,
+        queue: &CommandQueue,
+        mut dst: GpuBuffer<S>,
+        src_offset: usize,
+        dst_offset: usize,
+        size: usize,
+    ) -> Result<(GpuBuffer<InFlight>, GpuBuffer<InFlight>, Event)> {
+        let evt = queue
+            .enqueue_copy_buffer(&self.buf, &mut dst.buf, src_offset, dst_offset, size, &[])
+            .op(|| {
+                format!("copy of {size} bytes (src offset {src_offset}, dst offset {dst_offset})")
+            })?;
 
         Ok((
             GpuBuffer {
@@ -165,7 +233,105 @@ impl GpuBuffer<Written> {
                 len_bytes: self.len_bytes,
                 _state: PhantomData::<InFlight>,
             },
+            GpuBuffer {
+                buf: dst.buf,
+                len_bytes: dst.len_bytes,
+                _state: PhantomData::<InFlight>,
+            },
             evt,
         ))
     }
+
+    /// Blocking rectangular read (`clEnqueueReadBufferRect`); the buffer
+    /// is the source, `host` the destination of `rect`.
+    pub(crate) fn read_rect(
+        &self,
+        queue: &CommandQueue,
+        rect: &RectBytes,
+        host: &mut [u8],
+    ) -> Result<()> {
+        let _evt = queue
+            .enqueue_read_buffer_rect(
+                &self.buf,
+                CL_BLOCKING,
+                rect.src_origin.as_ptr(),
+                rect.dst_origin.as_ptr(),
+                rect.region.as_ptr(),
+                rect.src_row_pitch,
+                rect.src_slice_pitch,
+                rect.dst_row_pitch,
+                rect.dst_slice_pitch,
+                host.as_mut_ptr().cast(),
+                &[],
+            )
+            .op(|| {
+                format!(
+                    "rect read of region {:?} (bytes, rows, slices)",
+                    rect.region
+                )
+            })?;
+        Ok(())
+    }
+
+    /// Blocking rectangular write (`clEnqueueWriteBufferRect`); `host` is
+    /// the source, the buffer the destination of `rect`.
+    pub(crate) fn write_rect(
+        &mut self,
+        queue: &CommandQueue,
+        rect: &RectBytes,
+        host: &[u8],
+    ) -> Result<()> {
+        // Blockierend: der Treiber liest `host` nur während des Aufrufs
+        let _evt = queue
+            .enqueue_write_buffer_rect(
+                &mut self.buf,
+                CL_BLOCKING,
+                rect.dst_origin.as_ptr(),
+                rect.src_origin.as_ptr(),
+                rect.region.as_ptr(),
+                rect.dst_row_pitch,
+                rect.dst_slice_pitch,
+                rect.src_row_pitch,
+                rect.src_slice_pitch,
+                host.as_ptr().cast_mut().cast(),
+                &[],
+            )
+            .op(|| {
+                format!(
+                    "rect write of region {:?} (bytes, rows, slices)",
+                    rect.region
+                )
+            })?;
+        Ok(())
+    }
+
+    /// Device-side rectangular copy into `dst` (`clEnqueueCopyBufferRect`).
+    pub(crate) fn enqueue_copy_rect(
+        self,
+        queue: &CommandQueue,
+        mut dst: GpuBuffer<Written>,
+        rect: &RectBytes,
+    ) -> Result<(GpuBuffer<InFlight>, GpuBuffer<InFlight>, Event)> {
+        let evt = queue
+            .enqueue_copy_buffer_rect(
+                &self.buf,
+                &mut dst.buf,
+                rect.src_origin.as_ptr(),
+                rect.dst_origin.as_ptr(),
+                rect.region.as_ptr(),
+                rect.src_row_pitch,
+                rect.src_slice_pitch,
+                rect.dst_row_pitch,
+                rect.dst_slice_pitch,
+                &[],
+            )
+            .op(|| {
+                format!(
+                    "rect copy of region {:?} (bytes, rows, slices)",
+                    rect.region
+                )
+            })?;
+
+        Ok((self.into_inflight(), dst.into_inflight(), evt))
+    }
 }
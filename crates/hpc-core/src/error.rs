@@ -1,20 +1,116 @@
 //! Error handling for the high-level API.
 //!
-//! Defines a unified Error type wrapping OpenCL error codes
+//! Defines a unified Error type wrapping OpenCL error codes.
+//! OpenCL codes are printed by name (e.g. `CL_OUT_OF_RESOURCES (-5)`);
+//! `Error::Op` additionally names the failed operation and its parameters.
 
 use opencl3::error_codes::ClError;
+use opencl3::types::cl_int;
 
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error("OpenCL error {}", code_name(.0))]
     OpenCl(ClError),
+    /// An OpenCL call failed; `op` describes what was being done.
+    #[error("{op} failed: {}", code_name(.code))]
+    Op { op: String, code: ClError },
+    #[error("{0}")]
     Msg(String),
+    #[error("allocation failed: {0}")]
     AllocationFailed(String),
+    #[error("buffer size mismatch: expected {expected}, got {actual}")]
     BufferSizeMismatch { expected: usize, actual: usize },
+    /// No OpenCL device matched the selection criteria.
+    #[error("device not found: {0}")]
+    DeviceNotFound(String),
+    /// A wrapped raw handle does not belong to the given context/device.
+    #[error("handle mismatch: {0}")]
+    HandleMismatch(String),
+    /// The device aborted an enqueued command (negative execution status).
+    #[error("command {command} failed on the device: {}", code_name(.code))]
+    CommandFailed {
+        command: &'static str,
+        code: ClError,
+    },
+    /// Kernel arguments do not fit the kernel's parameter list.
+    #[error("kernel `{kernel}`: {msg}")]
+    KernelArgs { kernel: String, msg: String },
+    /// The NDRange does not fit the device or kernel limits.
+    #[error("kernel `{kernel}`: invalid NDRange: {msg}")]
+    InvalidNdRange { kernel: String, msg: String },
+    /// An element range is empty or does not lie within a buffer.
+    #[error(
+        "invalid range of {len} elements at offset {offset} for a buffer of {buffer_len} elements"
+    )]
+    OutOfBounds {
+        offset: usize,
+        len: usize,
+        buffer_len: usize,
+    },
+    /// A `BufferRect` has an empty dimension or pitches that do not fit its region.
+    #[error("invalid buffer rect: {0}")]
+    InvalidRect(String),
+    /// The device lacks a feature the operation needs.
+    #[error("not supported by the device: {0}")]
+    Unsupported(String),
+    /// Building, compiling or linking a program failed; `logs` holds the
+    /// compiler output of every device that produced one.
+    #[error("program {op} failed: {}{}", code_name(.code), format_logs(.logs))]
+    Build {
+        op: &'static str,
+        code: ClError,
+        logs: Vec<BuildLog>,
+    },
     // evtl. mehr Varianten ...
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Compiler output (`CL_PROGRAM_BUILD_LOG`) of one device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildLog {
+    /// Device name (`CL_DEVICE_NAME`).
+    pub device: String,
+    pub log: String,
+}
+
+impl Error {
+    /// The raw OpenCL error code, if this error came from an OpenCL call.
+    pub fn cl_code(&self) -> Option<cl_int> {
+        match self {
+            Error::OpenCl(code)
+            | Error::Op { code, .. }
+            | Error::CommandFailed { code, .. }
+            | Error::Build { code, .. } => Some(code.0),
+            _ => None,
+        }
+    }
+}
+
+fn code_name(code: &ClError) -> String {
+    format!("{code} ({})", code.0)
+}
+
+fn format_logs(logs: &[BuildLog]) -> String {
+    logs.iter()
+        .map(|l| format!("\n--- {} ---\n{}", l.device, l.log.trim_end()))
+        .collect()
+}
+
+/// Attach the failed operation to OpenCL errors (`Error::OpenCl` → `Error::Op`).
+pub(crate) trait ResultExt<T> {
+    fn op(self, op: impl FnOnce() -> String) -> Result<T>;
+}
+
+impl<T, E: Into<Error>> ResultExt<T> for std::result::Result<T, E> {
+    fn op(self, op: impl FnOnce() -> String) -> Result<T> {
+        self.map_err(|e| match e.into() {
+            Error::OpenCl(code) => Error::Op { op: op(), code },
+            other => other,
+        })
+    }
+}
+
 // --- Implementierungen für automatische Konvertierung --- //
 impl From<ClError> for Error {
     fn from(e: ClError) -> Self {
@@ -33,3 +129,64 @@ impl From<&str> for Error {
         Error::Msg(s.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opencl3::error_codes::{CL_BUILD_PROGRAM_FAILURE, CL_INVALID_VALUE, CL_OUT_OF_RESOURCES};
+
+    #[test]
+    fn prints_code_names() {
+        let e = Error::OpenCl(ClError(CL_OUT_OF_RESOURCES));
+        assert_eq!(e.to_string(), "OpenCL error CL_OUT_OF_RESOURCES (-5)");
+
+        let e: Error = Err::<(), _>(ClError(CL_INVALID_VALUE))
+            .op(|| "read 4 bytes".into())
+            .unwrap_err();
+        assert_eq!(e.to_string(), "read 4 bytes failed: CL_INVALID_VALUE (-30)");
+    }
+
+    #[test]
+    fn prints_build_logs_per_device() {
+        let e = Error::Build {
+            op: "build",
+            code: ClError(CL_BUILD_PROGRAM_FAILURE),
+            logs: vec![BuildLog {
+                device: "gpu0".into(),
+                log: "error: x undeclared\n\n".into(),
+            }],
+        };
+        assert_eq!(
+            e.to_string(),
+            "program build failed: CL_BUILD_PROGRAM_FAILURE (-11)\n--- gpu0 ---\nerror: x undeclared"
+        );
+    }
+
+    #[test]
+    fn cl_code_only_for_opencl_errors() {
+        let code = || ClError(CL_OUT_OF_RESOURCES);
+        assert_eq!(Error::OpenCl(code()).cl_code(), Some(-5));
+        let op = Error::Op {
+            op: "launch".into(),
+            code: code(),
+        };
+        assert_eq!(op.cl_code(), Some(-5));
+        let failed = Error::CommandFailed {
+            command: "CL_COMMAND_NDRANGE_KERNEL",
+            code: code(),
+        };
+        assert_eq!(failed.cl_code(), Some(-5));
+
+        assert_eq!(Error::Msg("x".into()).cl_code(), None);
+        let oob = Error::OutOfBounds {
+            offset: 8,
+            len: 4,
+            buffer_len: 10,
+        };
+        assert_eq!(oob.cl_code(), None);
+        assert_eq!(
+            oob.to_string(),
+            "invalid range of 4 elements at offset 8 for a buffer of 10 elements"
+        );
+    }
+}
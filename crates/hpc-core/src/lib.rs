@@ -17,25 +17,79 @@ pub mod api;
 
 // Re-export the main high-level API for easy access
 pub use api::{
+    AccessQualifier,
+    AddressQualifier,
+    ArgType,
+    Buf,
+    BufferRect,
+    BuildLog,
+    ClStd,
+    CompiledProgram,
     Context,
+    ContextBuilder,
     // Core types
+    Device,
     DeviceBuffer,
+    DeviceInfo,
+    DeviceType,
     // Error handling
     Error,
     // Utilities
+    EventStatus,
     EventToken,
+    InFlightBuffers,
     Kernel,
+    KernelArg,
+    KernelArgInfo,
+    KernelArgs,
+    KernelScalar,
+    KernelSignature,
+    KernelWorkGroupInfo,
+    LocalMem,
+    NdRange,
+    NoProfiling,
+    ProfileInfo,
+    Profiling,
+    ProfilingMode,
+    Program,
+    ProgramBuilder,
     Queue,
+    QueueBuilder,
     ReadGuard,
     Result,
+    TryWait,
+    TypeQualifiers,
+    Untyped,
+    WriteGuard,
 };
+#[cfg(feature = "queue_hints")]
+pub use api::{QueuePriority, QueueThrottle};
+
+// OpenCL vector types (float4, int2, ...)
+pub use api::vector;
 
 //#####
 // LOW-LEVEL RE-EXPORTS (for advanced users)
 //#####
 
 // Buffer states for advanced usage
-pub use buffer::state::{Empty, InFlight, Mapped, State, Written};
+pub use buffer::state::{Empty, InFlight, Mapped, Overwritable, State, Written};
+
+// #[derive(KernelArgs)], #[derive(KernelScalar)]
+pub use hpc_core_derive::{KernelArgs, KernelScalar};
+
+/// Items the derive macros expand to; not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::api::ArgBinder;
+
+    /// Supertrait sealing `KernelArgs`; only implemented for tuples and by
+    /// `#[derive(KernelArgs)]`.
+    pub trait ArgsSealed {}
+}
 
 // Low-level buffer for tests/benches
 pub use buffer::GpuBuffer;
+
+// Errors of event tokens that were dropped instead of waited on
+pub use buffer::{discarded_dropped_errors, take_dropped_errors};
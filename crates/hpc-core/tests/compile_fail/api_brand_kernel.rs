@@ -0,0 +1,25 @@
+use hpc_core::*;
+
+/*
+buffer of context A bound to a kernel of context B
+*/
+
+fn main() {
+    Context::with(|ctx_a| {
+        Context::with(|ctx_b| {
+            let queue_a = ctx_a.create_queue().unwrap();
+            let buf_a = ctx_a
+                .create_empty_buffer::<u32>(16).unwrap()
+                .write_block(&queue_a, &[0u32; 16]).unwrap();
+
+            let kernel_b = Kernel::from_source(ctx_b, "kernel void dummy(__global uint* buf) {}", "dummy").unwrap();
+
+            let queue_b = ctx_b.create_queue().unwrap();
+
+            // Verboten: Buffer aus Kontext A an Kernel aus Kontext B
+            let (_inflight, _evt) = kernel_b.launch(&queue_b, (buf_a,), 16).unwrap();
+        })
+        .unwrap();
+    })
+    .unwrap();
+}
@@ -0,0 +1,24 @@
+use hpc_core::*;
+
+/*
+buffer of context A read through a queue of context B
+*/
+
+fn main() {
+    Context::with(|ctx_a| {
+        Context::with(|ctx_b| {
+            let queue_a = ctx_a.create_queue().unwrap();
+            let queue_b = ctx_b.create_queue().unwrap();
+            let buf_a = ctx_a
+                .create_empty_buffer::<u32>(16).unwrap()
+                .write_block(&queue_a, &[0u32; 16]).unwrap();
+
+            let mut out = [0u32; 16];
+
+            // Verboten: Buffer aus Kontext A über Queue aus Kontext B lesen
+            buf_a.read_blocking(&queue_b, &mut out).unwrap();
+        })
+        .unwrap();
+    })
+    .unwrap();
+}
@@ -0,0 +1,18 @@
+use hpc_core::*;
+
+/*
+3D origin on a 2D rectangular transfer
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let queue = ctx.create_queue()?;
+
+    let grid = ctx.create_empty_buffer::<f32>(64 * 64)?.fill(&queue, 0.0)?;
+    let mut tile = vec![0.0f32; 16 * 16];
+
+    // Verboten: Origin hat 3 Einträge, der Bereich nur 2 Dimensionen
+    let rect = BufferRect::d2(16, 16).src_origin([16, 16, 1]).src_row_pitch(64);
+    grid.read_rect(&queue, &rect, &mut tile)?;
+    Ok(())
+}
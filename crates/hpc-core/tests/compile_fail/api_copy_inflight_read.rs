@@ -0,0 +1,20 @@
+use hpc_core::*;
+
+/*
+host read of the destination while a device-side copy is running
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let queue = ctx.create_queue()?;
+
+    let src = ctx.create_empty_buffer::<f32>(16)?.write_block(&queue, &[1.0; 16])?;
+    let dst = ctx.create_empty_buffer::<f32>(16)?;
+
+    let ((_src, dst), _evt) = src.copy_to(&queue, dst)?;
+
+    // Verboten: das Ziel ist bis zum wait() InFlight
+    let mut out = [0.0f32; 16];
+    dst.read_blocking(&queue, &mut out)?;
+    Ok(())
+}
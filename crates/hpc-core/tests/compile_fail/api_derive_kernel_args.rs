@@ -0,0 +1,14 @@
+use hpc_core::*;
+
+/*
+derived argument struct with a field that is no kernel argument
+*/
+
+#[derive(KernelArgs)]
+struct Scale<'brand> {
+    data: DeviceBuffer<'brand, f32, Written>,
+    // Verboten: Host-Vec ist weder Buffer noch KernelScalar
+    factors: Vec<f32>,
+}
+
+fn main() {}
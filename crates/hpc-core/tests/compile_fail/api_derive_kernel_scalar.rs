@@ -0,0 +1,16 @@
+use bytemuck::{Pod, Zeroable};
+use hpc_core::*;
+
+/*
+by-value struct argument with a field that has no OpenCL C equivalent
+*/
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, KernelScalar)]
+struct Params {
+    scale: f64,
+    // Verboten: usize hat auf dem Device eine andere Größe als auf dem Host
+    len: usize,
+}
+
+fn main() {}
@@ -13,5 +13,5 @@ fn main() {
     let buf = ctx.create_empty_buffer::<u8>(16).unwrap();
 
     let kernel = Kernel::from_source(&ctx, "kernel void dummy(__global uchar* buf) {}", "dummy").unwrap();
-    let (_inflight, _evt) = buf.enqueue_kernel(&queue, &kernel, 16).unwrap();
+    let (_inflight, _evt) = kernel.launch(&queue, (buf,), 16).unwrap();
 }
@@ -0,0 +1,17 @@
+use hpc_core::*;
+
+/*
+fill of a buffer whose previous fill is still running
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let queue = ctx.create_queue()?;
+
+    let buf = ctx.create_empty_buffer::<f32>(16)?;
+    let (buf, _evt) = buf.fill_non_blocking(&queue, 0.0)?;
+
+    // Verboten: nur Empty/Written dürfen überschrieben werden
+    let _buf = buf.fill(&queue, 1.0)?;
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use hpc_core::*;
+
+/*
+hand-written KernelArg that binds a borrowed buffer and keeps nothing in flight
+*/
+
+struct Sneaky<'a, 'brand>(&'a DeviceBuffer<'brand, f32, Written>);
+
+// Verboten: KernelArg ist versiegelt
+impl<'a, 'brand> KernelArg<'brand> for Sneaky<'a, 'brand> {
+    type InFlight = ();
+
+    fn bind(self, _binder: &mut __private::ArgBinder<'_, 'brand>) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn main() {}
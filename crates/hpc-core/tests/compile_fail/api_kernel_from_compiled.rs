@@ -0,0 +1,17 @@
+use hpc_core::*;
+
+/*
+kernel created from a compiled but unlinked program
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let object = ctx
+        .program_builder()
+        .source("kernel void k(__global float* a) {}")
+        .compile()?;
+
+    // Verboten: nur gelinkte Programme liefern Kernel
+    let _kernel = object.kernel("k")?;
+    Ok(())
+}
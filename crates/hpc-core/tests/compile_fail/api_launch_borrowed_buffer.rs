@@ -0,0 +1,25 @@
+use hpc_core::*;
+
+/*
+buffer argument bound by reference, so it would stay usable on the host
+while the kernel runs
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let queue = ctx.create_queue()?;
+    let kernel = Kernel::from_source(
+        &ctx,
+        "kernel void scale(__global float* a, uint n) {}",
+        "scale",
+    )?;
+
+    let a = ctx.create_empty_buffer::<f32>(16)?.write_block(&queue, &[1.0; 16])?;
+
+    // Verboten: Buffer-Argumente werden verschoben, nicht geliehen
+    let (_inflight, _evt) = kernel.launch(&queue, (&a, 16u32), 16)?;
+
+    let mut out = [0.0f32; 16];
+    a.read_blocking(&queue, &mut out)?;
+    Ok(())
+}
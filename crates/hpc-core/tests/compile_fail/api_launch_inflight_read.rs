@@ -0,0 +1,26 @@
+use hpc_core::*;
+
+/*
+host read of a buffer argument while a multi-buffer launch is running
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let queue = ctx.create_queue()?;
+    let kernel = Kernel::from_source(
+        &ctx,
+        "kernel void add(__global float* a, __global float* b, __global float* c, uint n) {}",
+        "add",
+    )?;
+
+    let a = ctx.create_empty_buffer::<f32>(16)?.write_block(&queue, &[1.0; 16])?;
+    let b = ctx.create_empty_buffer::<f32>(16)?.write_block(&queue, &[2.0; 16])?;
+    let c = ctx.create_empty_buffer::<f32>(16)?.write_block(&queue, &[0.0; 16])?;
+
+    let ((a, _b, _c, ()), _evt) = kernel.launch(&queue, (a, b, c, 16u32), 16)?;
+
+    // Verboten: auch Eingabe-Buffer sind während des Kernels InFlight
+    let mut out = [0.0f32; 16];
+    a.read_blocking(&queue, &mut out)?;
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use hpc_core::*;
+
+/*
+2D range with a 1D local size
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let queue = ctx.create_queue()?;
+    let kernel = Kernel::from_source(&ctx, "kernel void k(__global float* img) {}", "k")?;
+    let img = ctx.create_empty_buffer::<f32>(64)?.write_block(&queue, &[0.0; 64])?;
+
+    // Verboten: local size muss dieselbe Dimension haben wie die global size
+    let range = NdRange::d2(8, 8).local([64]);
+    let (_inflight, _evt) = kernel.launch(&queue, (img,), range)?;
+    Ok(())
+}
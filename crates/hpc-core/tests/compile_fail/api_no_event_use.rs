@@ -13,5 +13,5 @@ fn main() {
     let kernel = Kernel::from_source(&ctx, "kernel void dummy(__global uchar* buf) {}", "dummy").unwrap();
 
     // Komplettes Ignorieren – löst must_use-Fehler aus:
-    buf.enqueue_kernel(&queue, &kernel, 16).unwrap();
+    kernel.launch(&queue, (buf,), 16).unwrap();
 }
@@ -0,0 +1,18 @@
+use hpc_core::*;
+
+/*
+profiling timestamps requested for a command of a queue without profiling
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let queue = ctx.create_queue()?;
+
+    let buf = ctx.create_empty_buffer::<u32>(16)?
+        .write_block(&queue, &[0u32; 16])?;
+    let (inflight, token) = buf.write_non_block(&queue, &[1u32; 16])?;
+
+    // Verboten: Queue wurde ohne CL_QUEUE_PROFILING_ENABLE erstellt
+    let (_buf, _info) = token.wait_profiled(inflight)?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use hpc_core::*;
+
+/*
+typed kernel launched with swapped arguments
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let queue = ctx.create_queue()?;
+    let kernel = Kernel::from_source(
+        &ctx,
+        "kernel void scale(__global float* data, uint n) {}",
+        "scale",
+    )?
+    .typed::<(Buf<f32>, u32)>()?;
+
+    let data = ctx.create_empty_buffer::<f32>(16)?.write_block(&queue, &[1.0; 16])?;
+
+    // Verboten: Reihenfolge passt nicht zur Signatur (Buf<f32>, u32)
+    let (_inflight, _evt) = kernel.launch(&queue, (16u32, data), 16)?;
+    Ok(())
+}
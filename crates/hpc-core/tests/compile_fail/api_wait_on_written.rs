@@ -11,7 +11,7 @@ fn main() {
 
         let kernel = Kernel::from_source(&ctx, "kernel void dummy(__global uchar* buf) {}", "dummy").unwrap();
     // Kernel starten → InFlight + Event
-    let (inflight, _evt) = buf.enqueue_kernel(&queue, &kernel, 16).unwrap();
+    let ((inflight,), _evt) = kernel.launch(&queue, (buf,), 16).unwrap();
 
     let mut out = [0u8; 16];
 
@@ -0,0 +1,21 @@
+use hpc_core::*;
+
+/*
+host modification of the source slice while a non-blocking write is running
+*/
+
+fn main() -> Result<()> {
+    let ctx = Context::create_context()?;
+    let queue = ctx.create_queue()?;
+
+    let buf = ctx.create_empty_buffer::<f32>(16)?.fill(&queue, 0.0)?;
+    let mut window = vec![1.0f32; 4];
+
+    let (buf, guard) = buf.write_range_non_blocking(&queue, 4..8, &window)?;
+
+    // Verboten: der Treiber liest `window` noch
+    window[0] = 2.0;
+
+    let _buf = guard.wait(buf)?;
+    Ok(())
+}
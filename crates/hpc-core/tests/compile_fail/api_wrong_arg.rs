@@ -16,6 +16,6 @@ fn main() {
         "dummy"
     ).unwrap();
 
-    // wrong argument. Only scalar
-    let _illegal = kernel.set_arg_scalar(1, &buf).unwrap();
+    // wrong argument: host Vec instead of a scalar
+    let _illegal = kernel.launch(&queue, (buf, vec![16u32]), 16).unwrap();
 }